@@ -1,6 +1,8 @@
 use crate::lazy_unreachable;
 use crate::math;
 use crate::math::Point;
+use crate::rt;
+use crate::sdf;
 use crate::shape::Shape;
 use crate::storage::ContiguousMemory;
 use crate::storage::ContiguousMemoryMut;
@@ -8,6 +10,9 @@ use crate::storage::FromFn;
 use crate::storage::ReadStorage;
 use crate::storage::Storage;
 
+use num::Float;
+
+use std::collections::VecDeque;
 use std::ops::Deref;
 use std::ops::DerefMut;
 
@@ -50,10 +55,10 @@ impl<T: ?Sized, S, const B: usize> Chunk<T, S, B> {
 }
 
 impl<T: ?Sized, S: Copy, const B: usize> Chunk<T, S, B> {
-	pub fn as_ref(&self) -> ChunkRef<T, S, B> {
+	pub fn as_ref(&self) -> ChunkRef<'_, T, S, B> {
 		ChunkRef::new(&self.storage, self.shape)
 	}
-	pub fn as_mut(&mut self) -> ChunkMut<T, S, B> {
+	pub fn as_mut(&mut self) -> ChunkMut<'_, T, S, B> {
 		ChunkMut::new(&mut self.storage, self.shape)
 	}
 }
@@ -65,13 +70,13 @@ impl<'a, T: Clone, S, const B: usize> ChunkRef<'a, T, S, B> {
 }
 
 impl<T: ?Sized + Deref, S: Copy, const B: usize> Chunk<T, S, B> {
-	pub fn as_deref(&self) -> ChunkRef<T::Target, S, B> {
+	pub fn as_deref(&self) -> ChunkRef<'_, T::Target, S, B> {
 		ChunkRef::new(&*self.storage, self.shape)
 	}
 }
 
 impl<T: ?Sized + DerefMut, S: Copy, const B: usize> Chunk<T, S, B> {
-	pub fn as_deref_mut(&mut self) -> ChunkMut<T::Target, S, B> {
+	pub fn as_deref_mut(&mut self) -> ChunkMut<'_, T::Target, S, B> {
 		ChunkMut::new(&mut *self.storage, self.shape)
 	}
 }
@@ -109,6 +114,58 @@ impl<T: ?Sized + ContiguousMemory, S: Shape<B>, const B: usize> Chunk<T, S, B> {
 
 		self.storage.as_slice().get(index)
 	}
+	/// Halves the `Chunk` on every axis, each block of the result is `reduce` of the up to `2^B` blocks it covers
+	///
+	/// Block `p` of the result covers the blocks at `bounds.min + 2 * p + [0, 2)^B`, odd extents are rounded up.
+	/// See [`crate::reduce`] for common reductions
+	pub fn downsample<U>(
+		&self,
+		mut reduce: impl FnMut(&[&T::Item]) -> U,
+	) -> Chunk<Vec<U>, rt::Multiform<B>, B> {
+		let bounds = self.shape.bounds();
+		let shape = rt::Multiform::new(self.shape.extents().map(|extent| extent.div_ceil(2)));
+		let corners: Vec<_> = math::Aabb::<i32, B>::from_extents(math::Vector::repeat(2))
+			.points()
+			.collect();
+
+		let mut blocks = Vec::with_capacity(corners.len());
+
+		Chunk::from_shape_position(shape, |position| {
+			let min = bounds.min + position.coords * 2;
+
+			blocks.clear();
+			blocks.extend(
+				corners
+					.iter()
+					.filter_map(|corner| self.block(min + corner.coords)),
+			);
+
+			reduce(&blocks)
+		})
+	}
+	/// Signed euclidean distance field of the blocks for which `inside` returns `true`, see [`crate::sdf`]
+	///
+	/// Blocks outside of the `Chunk` are not taken into account
+	pub fn signed_distance<F: Float>(
+		&self,
+		mut inside: impl FnMut(&T::Item) -> bool,
+	) -> Chunk<Vec<F>, S, B> {
+		let grid = rt::Bounded::from(self.shape.bounds());
+
+		let mask: Vec<_> = math::Aabb::from(grid)
+			.points()
+			.map(|position| self.block(position).is_some_and(&mut inside))
+			.collect();
+		let distances = sdf::signed_distance::<F, B>(grid.extents(), &mask);
+
+		Chunk::from_shape_position(self.shape, |position| {
+			let index = grid
+				.position_to_index(position)
+				.unwrap_or_else(lazy_unreachable!());
+
+			distances[index]
+		})
+	}
 }
 
 impl<T: ?Sized + ContiguousMemoryMut, S: Shape<B>, const B: usize> Chunk<T, S, B> {
@@ -141,6 +198,151 @@ impl<T: ?Sized + ContiguousMemoryMut, S: Shape<B>, const B: usize> Chunk<T, S, B
 			Err(block)
 		}
 	}
+	/// Sets the face connected blocks reachable from `start` for which `predicate` returns `true` to `value`
+	///
+	/// Returns the number of written blocks
+	pub fn flood_fill(
+		&mut self,
+		start: Point<i32, B>,
+		mut predicate: impl FnMut(&T::Item) -> bool,
+		value: T::Item,
+	) -> usize
+	where
+		T::Item: Clone,
+	{
+		let offsets: Vec<_> = math::Neighborhood::VonNeumann(1).offsets().collect();
+
+		let mut visited = vec![false; self.shape.capacity()];
+		let mut queue = VecDeque::from([start]);
+		let mut filled = 0;
+
+		while let Some(position) = queue.pop_front() {
+			let Some(index) = self.shape.position_to_index(position) else {
+				continue;
+			};
+
+			let Some(seen) = visited.get_mut(index) else {
+				continue;
+			};
+
+			if std::mem::replace(seen, true) {
+				continue;
+			}
+
+			let Some(block) = self.storage.as_mut_slice().get_mut(index) else {
+				continue;
+			};
+
+			if predicate(block) {
+				*block = value.clone();
+				filled += 1;
+
+				queue.extend(offsets.iter().map(|&offset| position + offset));
+			}
+		}
+
+		filled
+	}
+	/// Advances every block to `rule(block, neighbors)` at once, `neighbors` is in the order of [`math::Neighborhood::offsets`]
+	///
+	/// Blocks outside of the `Chunk` are default
+	pub fn step(
+		&mut self,
+		neighborhood: math::Neighborhood,
+		mut rule: impl FnMut(&T::Item, &[&T::Item]) -> T::Item,
+	) where
+		T::Item: Default,
+	{
+		let offsets: Vec<_> = neighborhood.offsets().collect();
+		let default = T::Item::default();
+
+		let slice = self.storage.as_slice();
+		let mut neighbors = Vec::with_capacity(offsets.len());
+
+		let next: Vec<_> = slice
+			.iter()
+			.enumerate()
+			.map(|(index, block)| {
+				let position = self
+					.shape
+					.index_to_position(index)
+					.unwrap_or_else(lazy_unreachable!());
+
+				neighbors.clear();
+				neighbors.extend(offsets.iter().map(|&offset| {
+					self.shape
+						.position_to_index(position + offset)
+						.and_then(|index| slice.get(index))
+						.unwrap_or(&default)
+				}));
+
+				rule(block, &neighbors)
+			})
+			.collect();
+
+		for (block, next) in self.storage.as_mut_slice().iter_mut().zip(next) {
+			*block = next;
+		}
+	}
+	/// Sets every block to `f(block, other)` with the block of `other` at the same position
+	///
+	/// See [`crate::sdf`] for operations combining distance fields
+	///
+	/// # Panics
+	/// This function panics if `self.shape != other.shape`
+	pub fn combine<U: ?Sized + ContiguousMemory>(
+		&mut self,
+		other: &Chunk<U, S, B>,
+		mut f: impl FnMut(&T::Item, &U::Item) -> T::Item,
+	) {
+		assert!(self.shape == other.shape);
+
+		for (block, other) in self
+			.storage
+			.as_mut_slice()
+			.iter_mut()
+			.zip(other.storage.as_slice())
+		{
+			*block = f(block, other);
+		}
+	}
+	/// Sets every block inside of `primitive` to `f(position)`
+	pub fn rasterize(
+		&mut self,
+		primitive: impl math::Primitive<B>,
+		mut f: impl FnMut(Point<i32, B>) -> T::Item,
+	) {
+		let Some(part) = primitive
+			.bounds()
+			.intersection(&math::Aabb::from_shape(&self.shape))
+		else {
+			return;
+		};
+
+		for position in part
+			.points()
+			.filter(|&position| primitive.contains(position))
+		{
+			if let Some(block) = self.block_mut(position) {
+				*block = f(position);
+			}
+		}
+	}
+	/// Sets every block at a position of `line` to `value`, positions outside of the `Chunk` are skipped
+	///
+	/// Returns the number of written blocks, see [`math::Bresenham`] and [`math::Supercover`]
+	pub fn draw_line(
+		&mut self,
+		line: impl IntoIterator<Item = Point<i32, B>>,
+		value: T::Item,
+	) -> usize
+	where
+		T::Item: Clone,
+	{
+		line.into_iter()
+			.filter_map(|position| self.replace(position, value.clone()).ok())
+			.count()
+	}
 }
 
 impl<T, S: Shape<B>, const B: usize> Chunk<T, S, B>
@@ -153,10 +355,10 @@ where
 		Self::new(T::from_fn(capacity, f), shape)
 	}
 	pub fn from_shape_position(shape: S, mut f: impl FnMut(Point<i32, B>) -> T::Item) -> Self {
-		let extents = shape.extents();
-
 		Self::from_shape_index(shape, |index| {
-			f(math::index_to_position(extents, index).unwrap_or_else(lazy_unreachable!()))
+			f(shape
+				.index_to_position(index)
+				.unwrap_or_else(lazy_unreachable!()))
 		})
 	}
 	pub fn from_shape_default(shape: S) -> Self
@@ -202,3 +404,126 @@ where
 
 pub type ChunkRef<'a, T, S, const B: usize> = Chunk<&'a T, S, B>;
 pub type ChunkMut<'a, T, S, const B: usize> = Chunk<&'a mut T, S, B>;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::primitive::Ball;
+	use crate::math::Primitive;
+
+	#[test]
+	fn test_rasterize() {
+		let mut chunk = Chunk::<Vec<u8>, ct::Uniform<6, 2>, 2>::default();
+
+		// partly outside of the `Chunk`
+		let ball = Ball::new(Point::from([4.0, 1.0]), 2.0);
+
+		chunk.rasterize(ball, |position| position.x as u8);
+
+		for (position, &block) in chunk.block_positions() {
+			let expected = if ball.contains(position) {
+				position.x as u8
+			} else {
+				0
+			};
+
+			assert_eq!(block, expected);
+		}
+
+		assert_eq!(chunk.block(Point::from([4, 0])), Some(&4));
+		assert_eq!(chunk.block(Point::from([4, 4])), Some(&0));
+
+		chunk.rasterize(Ball::new(Point::from([-9.0, -9.0]), 2.0), |_| 7);
+
+		assert!(chunk.iter().all(|&block| block != 7));
+	}
+
+	#[test]
+	fn test_flood_fill() {
+		// two rooms split by a wall at `x = 2` with a gap at `y = 4`
+		let mut chunk = Chunk::<Vec<u8>, ct::Uniform<5, 2>, 2>::from_position(|position| {
+			u8::from(position.x == 2 && position.y != 4)
+		});
+
+		assert_eq!(
+			chunk.flood_fill(Point::from([0, 0]), |&block| block == 0, 2),
+			5 * 5 - 4
+		);
+		assert_eq!(chunk.block(Point::from([4, 0])), Some(&2));
+		assert_eq!(chunk.block(Point::from([2, 0])), Some(&1));
+
+		// closing the gap keeps the fill on one side
+		*chunk.block_mut(Point::from([2, 4])).unwrap() = 1;
+
+		assert_eq!(
+			chunk.flood_fill(Point::from([0, 0]), |&block| block == 2, 3),
+			5 * 2
+		);
+		assert_eq!(chunk.block(Point::from([4, 0])), Some(&2));
+
+		// starting outside of the `Chunk` or on a rejected block fills nothing
+		assert_eq!(chunk.flood_fill(Point::from([-1, 0]), |_| true, 4), 0);
+		assert_eq!(
+			chunk.flood_fill(Point::from([2, 0]), |&block| block == 0, 4),
+			0
+		);
+	}
+
+	#[test]
+	fn test_draw_line() {
+		let mut chunk = Chunk::<Vec<u8>, ct::Uniform<4, 2>, 2>::default();
+
+		let line = || math::Supercover::new(Point::from([-1, 0]), Point::from([4, 2]));
+
+		// `[-1, 0]` and `[4, 2]` are outside of the `Chunk`
+		assert_eq!(chunk.draw_line(line(), 1), line().count() - 2);
+
+		for position in line() {
+			let expected = (0..4).contains(&position.x).then_some(&1);
+
+			assert_eq!(chunk.block(position), expected);
+		}
+
+		assert_eq!(
+			chunk.iter().filter(|&&block| block == 1).count(),
+			line().count() - 2
+		);
+		assert_eq!(
+			chunk.draw_line(
+				math::Bresenham::new(Point::from([0, 3]), Point::from([3, 0])),
+				2
+			),
+			4
+		);
+		assert_eq!(chunk.block(Point::from([1, 2])), Some(&2));
+	}
+
+	#[test]
+	fn test_step() {
+		type Chunk = super::Chunk<Vec<bool>, ct::Uniform<5, 2>, 2>;
+
+		let life = |&alive: &bool, neighbors: &[&bool]| {
+			let count = neighbors.iter().filter(|&&&neighbor| neighbor).count();
+
+			matches!((alive, count), (true, 2) | (_, 3))
+		};
+
+		let cells = |cells: &[[i32; 2]]| {
+			Chunk::from_position(|position| cells.contains(&[position.x, position.y]))
+		};
+
+		let mut chunk = cells(&[[2, 1], [2, 2], [2, 3]]);
+
+		chunk.step(math::Neighborhood::Moore(1), life);
+
+		assert_eq!(chunk, cells(&[[1, 2], [2, 2], [3, 2]]));
+
+		// nothing grows outside of the `Chunk`
+		let mut chunk = cells(&[[0, 0], [0, 1], [0, 2]]);
+
+		chunk.step(math::Neighborhood::Moore(1), life);
+
+		assert_eq!(chunk, cells(&[[0, 1], [1, 1]]));
+	}
+}
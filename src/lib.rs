@@ -3,7 +3,11 @@
 
 pub mod chunk;
 pub mod math;
+pub mod mesh;
+pub mod octree;
 pub mod position_map;
+pub mod reduce;
+pub mod sdf;
 pub mod shape;
 pub mod storage;
 pub mod world;
@@ -13,8 +17,10 @@ pub use position_map::PositionMap;
 pub use shape::ct;
 pub use shape::rt;
 
+pub use shape::Layout;
 pub use shape::Shape;
 pub use shape::UniformShape;
+pub use shape::WithLayout;
 
 // pub use shape::Cow;
 pub(crate) use shape::UniformWorldCoordinate;
@@ -40,6 +46,7 @@ macro_rules! make_prelude {
 			pub mod rt {
 				pub type Uniform = $crate::shape::rt::Uniform<$D>;
 				pub type Multiform = $crate::shape::rt::Multiform<$D>;
+				pub type Bounded = $crate::shape::rt::Bounded<$D>;
 			}
 
 			pub type PositionMap<T> = $crate::PositionMap<T, $D>;
@@ -51,6 +58,7 @@ macro_rules! make_prelude {
 			// pub trait Shape = $crate::Shape<$D>;
 			// pub trait UniformShape = $crate::UniformShape<$D>;
 
+			pub use $crate::Layout;
 			pub use $crate::Shape;
 			pub use $crate::UniformShape;
 
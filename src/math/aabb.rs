@@ -0,0 +1,273 @@
+use super::*;
+
+use crate::shape::rt;
+use crate::Shape;
+
+/// Axis aligned box covering `[min, max)`
+///
+/// A box is empty if `min >= max` on any axis
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Aabb<S: Coordinate, const D: usize> {
+	pub min: Point<S, D>,
+	pub max: Point<S, D>,
+}
+
+#[inline(always)]
+fn min<S: Coordinate>(a: S, b: S) -> S {
+	if b < a {
+		b
+	} else {
+		a
+	}
+}
+
+#[inline(always)]
+fn max<S: Coordinate>(a: S, b: S) -> S {
+	if b > a {
+		b
+	} else {
+		a
+	}
+}
+
+impl<S: Coordinate, const D: usize> Aabb<S, D> {
+	pub fn new(min: Point<S, D>, max: Point<S, D>) -> Self {
+		Self { min, max }
+	}
+	/// Box covering `[0, extents)`
+	pub fn from_extents(extents: Vector<usize, D>) -> Self {
+		Self::new(
+			Point::origin(),
+			matrix_cast(extents).expect("`extents` out of range").into(),
+		)
+	}
+	/// Box covering the blocks of `shape`, see [`Shape::bounds`]
+	pub fn from_shape(shape: &impl Shape<D>) -> Self {
+		let bounds = shape.bounds();
+
+		Self::new(
+			point_cast(bounds.min).expect("`bounds` out of range"),
+			point_cast(bounds.max).expect("`bounds` out of range"),
+		)
+	}
+	/// Smallest box containing both `a` and `b`
+	pub fn from_corners(a: Point<S, D>, b: Point<S, D>) -> Self {
+		Self::new(
+			a.coords.zip_map(&b.coords, min).into(),
+			a.coords.zip_map(&b.coords, max).into(),
+		)
+	}
+	pub fn size(&self) -> Vector<S, D> {
+		self.max.coords.zip_map(&self.min.coords, |max, min| {
+			if max > min {
+				max - min
+			} else {
+				S::zero()
+			}
+		})
+	}
+	/// Extents of `self`, `0` on axes where `self` is empty
+	pub fn extents(&self) -> Vector<usize, D> {
+		self.size()
+			.map(|extent| extent.to_usize().expect("`extent` out of range"))
+	}
+	pub fn is_empty(&self) -> bool {
+		self.min
+			.iter()
+			.zip(self.max.iter())
+			.any(|(min, max)| min >= max)
+	}
+	pub fn volume(&self) -> S {
+		self.size()
+			.iter()
+			.fold(S::one(), |acc, &extent| acc * extent)
+	}
+	pub fn contains(&self, point: Point<S, D>) -> bool {
+		(0..D).all(|i| self.min[i] <= point[i] && point[i] < self.max[i])
+	}
+	/// `true` if every point of `other` is also in `self`, empty boxes are contained by every box
+	pub fn contains_aabb(&self, other: &Self) -> bool {
+		other.is_empty()
+			|| (0..D).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+	}
+	/// Overlapping part of `self` and `other`, `None` if they do not overlap
+	pub fn intersection(&self, other: &Self) -> Option<Self> {
+		let intersection = Self::new(
+			self.min.coords.zip_map(&other.min.coords, max).into(),
+			self.max.coords.zip_map(&other.max.coords, min).into(),
+		);
+
+		(!intersection.is_empty()).then_some(intersection)
+	}
+	/// Smallest box containing both `self` and `other`
+	pub fn union(&self, other: &Self) -> Self {
+		if self.is_empty() {
+			return *other;
+		}
+
+		if other.is_empty() {
+			return *self;
+		}
+
+		Self::new(
+			self.min.coords.zip_map(&other.min.coords, min).into(),
+			self.max.coords.zip_map(&other.max.coords, max).into(),
+		)
+	}
+	/// Grows `self` by `amount` on every side, negative `amount`s shrink it
+	pub fn expand(&self, amount: Vector<S, D>) -> Self {
+		Self::new(self.min - amount, self.max + amount)
+	}
+	pub fn translate(&self, offset: Vector<S, D>) -> Self {
+		Self::new(self.min + offset, self.max + offset)
+	}
+}
+
+impl<const D: usize> Aabb<i32, D> {
+	/// Every integer point in `self` with the first axis varying fastest
+	pub fn points(&self) -> Points<D> {
+		Points::new(*self)
+	}
+}
+
+impl<const D: usize> IntoIterator for Aabb<i32, D> {
+	type Item = Point<i32, D>;
+	type IntoIter = Points<D>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.points()
+	}
+}
+
+impl<const D: usize> From<rt::Bounded<D>> for Aabb<i32, D> {
+	fn from(shape: rt::Bounded<D>) -> Self {
+		Self::new(shape.min(), shape.max())
+	}
+}
+
+impl<const D: usize> From<Aabb<i32, D>> for rt::Bounded<D> {
+	/// Empty boxes become empty [`rt::Bounded`]s at `aabb.min`
+	fn from(aabb: Aabb<i32, D>) -> Self {
+		rt::Bounded::new(aabb.min, aabb.min + aabb.size())
+	}
+}
+
+/// Iterator over every integer point in an [`Aabb`], see [`Aabb::points`]
+#[derive(Debug, Clone)]
+pub struct Points<const D: usize> {
+	aabb: Aabb<i32, D>,
+	next: Option<Point<i32, D>>,
+	remaining: usize,
+}
+
+impl<const D: usize> Points<D> {
+	fn new(aabb: Aabb<i32, D>) -> Self {
+		let empty = aabb.is_empty();
+
+		Self {
+			aabb,
+			next: (!empty).then_some(aabb.min),
+			remaining: if empty { 0 } else { aabb.extents().product() },
+		}
+	}
+}
+
+impl<const D: usize> Iterator for Points<D> {
+	type Item = Point<i32, D>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let current = self.next?;
+
+		let mut next = current;
+
+		self.next = (0..D).find_map(|i| {
+			next[i] += 1;
+
+			if next[i] < self.aabb.max[i] {
+				return Some(next);
+			}
+
+			next[i] = self.aabb.min[i];
+
+			None
+		});
+		self.remaining -= 1;
+
+		Some(current)
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.remaining, Some(self.remaining))
+	}
+}
+
+impl<const D: usize> ExactSizeIterator for Points<D> {}
+
+impl<const D: usize> std::iter::FusedIterator for Points<D> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn aabb(min: [i32; 2], max: [i32; 2]) -> Aabb<i32, 2> {
+		Aabb::new(Point::from(min), Point::from(max))
+	}
+
+	#[test]
+	fn test_intersection_union() {
+		let a = aabb([0, 0], [4, 4]);
+		let b = aabb([2, -2], [6, 3]);
+
+		assert_eq!(a.intersection(&b), Some(aabb([2, 0], [4, 3])));
+		assert_eq!(a.union(&b), aabb([0, -2], [6, 4]));
+		assert_eq!(a.intersection(&aabb([4, 0], [5, 4])), None);
+		assert_eq!(a.union(&aabb([9, 9], [9, 9])), a);
+		assert!(a.contains_aabb(&aabb([1, 1], [3, 4])));
+		assert!(!a.contains_aabb(&b));
+	}
+
+	#[test]
+	fn test_contains_volume() {
+		let a = aabb([-1, 0], [2, 2]);
+
+		assert!(a.contains(Point::from([-1, 1])));
+		assert!(!a.contains(Point::from([2, 1])));
+		assert_eq!(a.volume(), 6);
+		assert_eq!(a.extents(), Vector::from([3, 2]));
+		assert_eq!(aabb([0, 0], [-1, 2]).volume(), 0);
+		assert_eq!(a.expand(Vector::from([1, 1])), aabb([-2, -1], [3, 3]));
+		assert_eq!(a.translate(Vector::from([1, -1])), aabb([0, -1], [3, 1]));
+	}
+
+	#[test]
+	fn test_points() {
+		let a = aabb([-1, 3], [1, 6]);
+
+		let points: Vec<_> = a.points().collect();
+
+		assert_eq!(points.len(), 6);
+		assert_eq!(a.points().len(), 6);
+		assert_eq!(points[0], Point::from([-1, 3]));
+		assert_eq!(points[1], Point::from([0, 3]));
+		assert_eq!(points[2], Point::from([-1, 4]));
+		assert_eq!(points[5], Point::from([0, 5]));
+		assert!(points.iter().all(|&point| a.contains(point)));
+		assert_eq!(aabb([0, 0], [0, 5]).points().count(), 0);
+	}
+
+	#[test]
+	fn test_shape_conversion() {
+		let shape = crate::ct::Multiform2::<3, 4>::new();
+
+		assert_eq!(Aabb::<i32, 2>::from_shape(&shape), aabb([0, 0], [3, 4]));
+
+		let bounded = rt::Bounded::centered(Vector::from([1, 2]));
+
+		assert_eq!(Aabb::from(bounded), aabb([-1, -2], [2, 3]));
+		assert_eq!(Aabb::from_shape(&bounded), aabb([-1, -2], [2, 3]));
+		assert_eq!(rt::Bounded::from(Aabb::from(bounded)), bounded);
+	}
+}
@@ -0,0 +1,259 @@
+use super::*;
+
+/// Bresenham rasterization of the segment between two points in `D` dimensions
+///
+/// Yields exactly one point per step along the axis with the largest difference, both ends included
+#[derive(Debug, Clone)]
+pub struct Bresenham<const D: usize> {
+	position: Point<i32, D>,
+	step: Vector<i32, D>,
+	delta: Vector<i64, D>,
+	error: Vector<i64, D>,
+	length: i64,
+	remaining: usize,
+}
+
+impl<const D: usize> Bresenham<D> {
+	pub fn new(from: Point<i32, D>, to: Point<i32, D>) -> Self {
+		let difference = (to - from).map(i64::from);
+		let delta = difference.abs();
+		let length = delta.max();
+
+		Self {
+			position: from,
+			step: difference.map(|c| c.signum() as i32),
+			delta,
+			error: delta.map(|c| 2 * c - length),
+			length,
+			remaining: length as usize + 1,
+		}
+	}
+}
+
+impl<const D: usize> Iterator for Bresenham<D> {
+	type Item = Point<i32, D>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.remaining = self.remaining.checked_sub(1)?;
+
+		let position = self.position;
+
+		if self.remaining > 0 {
+			for i in 0..D {
+				if self.error[i] > 0 {
+					self.position[i] += self.step[i];
+					self.error[i] -= 2 * self.length;
+				}
+
+				self.error[i] += 2 * self.delta[i];
+			}
+		}
+
+		Some(position)
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.remaining, Some(self.remaining))
+	}
+}
+
+impl<const D: usize> ExactSizeIterator for Bresenham<D> {}
+
+impl<const D: usize> std::iter::FusedIterator for Bresenham<D> {}
+
+/// Every cell the segment between the centers of two cells touches in `D` dimensions
+///
+/// Where the segment passes exactly through an edge or a corner every cell touching it is yielded, by
+/// increasing set of crossed axes. Consecutive points otherwise share a face
+///
+/// # Panics
+/// [`Supercover::new`] panics if `D` is greater than `64`
+#[derive(Debug, Clone)]
+pub struct Supercover<const D: usize> {
+	position: Point<i32, D>,
+	step: Vector<i32, D>,
+	delta: Vector<i64, D>,
+	crossed: Vector<i64, D>,
+	/// Axes whose boundaries the segment crosses next, all at once
+	axes: u64,
+	/// Subset of `axes` along which the next cell is offset from `position`
+	subset: u64,
+	done: bool,
+}
+
+impl<const D: usize> Supercover<D> {
+	pub fn new(from: Point<i32, D>, to: Point<i32, D>) -> Self {
+		assert!(D <= 64, "more than `64` dimensions");
+
+		let difference = (to - from).map(i64::from);
+
+		Self {
+			position: from,
+			step: difference.map(|c| c.signum() as i32),
+			delta: difference.abs(),
+			crossed: Vector::zeros(),
+			axes: 0,
+			subset: 0,
+			done: false,
+		}
+	}
+	fn offset(&self, axes: u64) -> Point<i32, D> {
+		let mut position = self.position;
+
+		for i in (0..D).filter(|&i| axes >> i & 1 == 1) {
+			position[i] += self.step[i];
+		}
+
+		position
+	}
+}
+
+impl<const D: usize> Iterator for Supercover<D> {
+	type Item = Point<i32, D>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.axes != 0 {
+			let subset = self.subset;
+
+			// the next subset of `axes` in increasing order
+			self.subset = subset.wrapping_sub(self.axes) & self.axes;
+
+			if subset != self.axes {
+				return Some(self.offset(subset));
+			}
+
+			self.position = self.offset(self.axes);
+
+			for i in (0..D).filter(|&i| self.axes >> i & 1 == 1) {
+				self.crossed[i] += 1;
+			}
+
+			self.axes = 0;
+		}
+
+		if self.done {
+			return None;
+		}
+
+		// the segment crosses its `k`th boundary on axis `i` at `(2k + 1) / (2 * delta[i])`
+		let crossing = |i: usize| (2 * self.crossed[i] + 1, self.delta[i]);
+		let earlier = |(a, da): (i64, i64), (b, db): (i64, i64)| (a * db).cmp(&(b * da));
+
+		let axes = (0..D).filter(|&i| self.crossed[i] < self.delta[i]);
+
+		match axes.clone().map(crossing).min_by(|&a, &b| earlier(a, b)) {
+			Some(first) => {
+				self.axes = axes
+					.filter(|&i| earlier(crossing(i), first).is_eq())
+					.fold(0, |axes, i| axes | 1 << i);
+				self.subset = self.axes & self.axes.wrapping_neg();
+			}
+			None => self.done = true,
+		}
+
+		Some(self.position)
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		if self.done && self.axes == 0 {
+			return (0, Some(0));
+		}
+
+		let remaining = (self.delta - self.crossed).sum() as usize;
+
+		(remaining + 1, None)
+	}
+}
+
+impl<const D: usize> std::iter::FusedIterator for Supercover<D> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_bresenham() {
+		assert_eq!(
+			Bresenham::new(Point::from([0, 0]), Point::from([5, 2])).collect::<Vec<_>>(),
+			[[0, 0], [1, 0], [2, 1], [3, 1], [4, 2], [5, 2]].map(Point::from)
+		);
+
+		let line = Bresenham::new(Point::from([3, -2, 7, 0]), Point::from([-4, 5, 1, 2]));
+
+		assert_eq!(line.len(), 8);
+
+		let points: Vec<_> = line.collect();
+
+		assert_eq!(points.last(), Some(&Point::from([-4, 5, 1, 2])));
+
+		for pair in points.windows(2) {
+			assert!((pair[1] - pair[0]).iter().all(|c| c.abs() <= 1));
+		}
+
+		assert_eq!(
+			Bresenham::new(Point::from([1, 1]), Point::from([1, 1])).collect::<Vec<_>>(),
+			[Point::from([1, 1])]
+		);
+	}
+
+	#[test]
+	fn test_supercover() {
+		assert_eq!(
+			Supercover::new(Point::from([0, 0]), Point::from([2, 1])).collect::<Vec<_>>(),
+			[[0, 0], [1, 0], [1, 1], [2, 1]].map(Point::from)
+		);
+		// both cells next to a crossed corner are included
+		assert_eq!(
+			Supercover::new(Point::from([0, 0]), Point::from([-1, 1])).collect::<Vec<_>>(),
+			[[0, 0], [-1, 0], [0, 1], [-1, 1]].map(Point::from)
+		);
+		assert_eq!(
+			Supercover::new(Point::from([0, 0, 0]), Point::from([1, 1, 1])).count(),
+			8
+		);
+
+		let line = Supercover::new(Point::from([3, -2, 7]), Point::from([-4, 5, 1]));
+		let points: Vec<_> = line.collect();
+
+		assert_eq!(points.last(), Some(&Point::from([-4, 5, 1])));
+
+		for pair in points.windows(2) {
+			assert!((pair[1] - pair[0]).iter().all(|c| c.abs() <= 1));
+		}
+
+		for (from, to) in [
+			([0, 0], [6, 3]),
+			([2, -1], [-4, 2]),
+			([0, 0], [3, 3]),
+			([1, 5], [1, -2]),
+		] {
+			let (from, to) = (Point::from(from), Point::from(to));
+
+			let bounds = Aabb::from_corners(from, to);
+
+			let mut expected: Vec<_> = Aabb::new(bounds.min, bounds.max + Vector::repeat(1))
+				.points()
+				.filter(|&cell| touches(from, to, cell))
+				.collect();
+			let mut points: Vec<_> = Supercover::new(from, to).collect();
+
+			expected.sort_by_key(|point| (point.x, point.y));
+			points.sort_by_key(|point| (point.x, point.y));
+
+			assert_eq!(points, expected);
+		}
+	}
+
+	/// Whether the segment between the centers of `from` and `to` touches the closed cell `cell`
+	fn touches(from: Point<i32, 2>, to: Point<i32, 2>, cell: Point<i32, 2>) -> bool {
+		let direction = to - from;
+		let normal = Vector::from([-direction.y, direction.x]);
+
+		// in half cells, the cell spans one unit on each side of its center
+		let distance = normal.dot(&((cell - from) * 2)).abs();
+
+		(0..2).all(|i| {
+			let (min, max) = (from[i].min(to[i]) * 2, from[i].max(to[i]) * 2);
+
+			cell[i] * 2 + 1 >= min && cell[i] * 2 - 1 <= max
+		}) && distance <= normal.abs().sum()
+	}
+}
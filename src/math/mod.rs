@@ -1,10 +1,23 @@
 /// `Nalgebra` re-export
+pub mod aabb;
+pub mod line;
+pub mod neighborhood;
 pub mod position_index_conversion;
+pub mod primitive;
+pub mod raycast;
 pub mod uniform_position_index_conversion;
 pub mod world_chunk_block_conversion;
 
+pub use aabb::Aabb;
+pub use line::Bresenham;
+pub use line::Supercover;
+pub use neighborhood::Neighborhood;
 pub use position_index_conversion::index_to_position;
+pub use position_index_conversion::index_to_position_offset;
 pub use position_index_conversion::position_to_index;
+pub use position_index_conversion::position_to_index_offset;
+pub use primitive::Primitive;
+pub use raycast::Traversal;
 pub use world_chunk_block_conversion::chunk_block_to_world;
 pub use world_chunk_block_conversion::world_to_chunk_block;
 
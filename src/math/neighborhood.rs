@@ -0,0 +1,63 @@
+use super::*;
+
+/// Set of offsets around a point, the point itself is never part of it
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Neighborhood {
+	/// Offsets with a manhattan length of at most `radius`, `2 * D` neighbors with a `radius` of `1`
+	VonNeumann(u32),
+	/// Offsets with a chebyshev length of at most `radius`, `3^D - 1` neighbors with a `radius` of `1`
+	Moore(u32),
+}
+
+impl Neighborhood {
+	pub fn radius(&self) -> u32 {
+		match *self {
+			Self::VonNeumann(radius) | Self::Moore(radius) => radius,
+		}
+	}
+	pub fn contains<const D: usize>(&self, offset: Vector<i32, D>) -> bool {
+		if offset.iter().all(|&c| c == 0) {
+			return false;
+		}
+
+		let radius = self.radius();
+
+		match self {
+			Self::VonNeumann(_) => offset.iter().map(|c| c.unsigned_abs()).sum::<u32>() <= radius,
+			Self::Moore(_) => offset.iter().all(|c| c.unsigned_abs() <= radius),
+		}
+	}
+	/// Every offset in this [`Neighborhood`] with the first axis varying fastest
+	pub fn offsets<const D: usize>(self) -> impl Iterator<Item = Vector<i32, D>> {
+		let radius = self.radius() as i32;
+
+		Aabb::new(
+			Point::from(Vector::repeat(-radius)),
+			Point::from(Vector::repeat(radius + 1)),
+		)
+		.points()
+		.map(|point| point.coords)
+		.filter(move |&offset| self.contains(offset))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_offsets() {
+		assert_eq!(Neighborhood::VonNeumann(1).offsets::<3>().count(), 6);
+		assert_eq!(Neighborhood::Moore(1).offsets::<3>().count(), 26);
+		assert_eq!(Neighborhood::Moore(1).offsets::<4>().count(), 80);
+		assert_eq!(Neighborhood::VonNeumann(2).offsets::<2>().count(), 12);
+		assert_eq!(Neighborhood::Moore(2).offsets::<2>().count(), 24);
+		assert!(Neighborhood::Moore(1)
+			.offsets::<2>()
+			.all(|offset| offset != Vector::<i32, 2>::zeros()));
+	}
+}
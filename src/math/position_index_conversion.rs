@@ -94,7 +94,7 @@ mod tests {
 				position_to_index::<i32, 5>(Vector::from([X, Y, Z, W, V]), position.cast::<i32>())
 					.unwrap();
 
-			assert_eq!(expected as usize, result);
+			assert_eq!(expected, result);
 		})
 	}
 	#[test]
@@ -0,0 +1,325 @@
+use super::*;
+
+use num::Float;
+
+/// A solid which can be rasterized into integer cells
+///
+/// Cell `p` covers `[p, p + 1)` and is inside of a [`Primitive`] if its center `p + 0.5` is
+pub trait Primitive<const D: usize> {
+	/// No cell outside of these bounds is inside of the [`Primitive`]
+	fn bounds(&self) -> Aabb<i32, D>;
+	fn contains(&self, position: Point<i32, D>) -> bool;
+	/// Every cell inside of the [`Primitive`] with the first axis varying fastest
+	fn positions(&self) -> impl '_ + Iterator<Item = Point<i32, D>> {
+		self.bounds()
+			.points()
+			.filter(move |&position| self.contains(position))
+	}
+}
+
+impl<T: Primitive<D>, const D: usize> Primitive<D> for &T {
+	fn bounds(&self) -> Aabb<i32, D> {
+		(*self).bounds()
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		(*self).contains(position)
+	}
+}
+
+/// Axis aligned box
+impl<const D: usize> Primitive<D> for Aabb<i32, D> {
+	fn bounds(&self) -> Aabb<i32, D> {
+		*self
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		Aabb::contains(self, position)
+	}
+}
+
+fn center<F: Coordinate + Float, const D: usize>(position: Point<i32, D>) -> Point<F, D> {
+	let half = F::from(0.5).unwrap_or_else(crate::lazy_unreachable!());
+
+	position.map(|c| F::from(c).unwrap_or_else(crate::lazy_unreachable!()) + half)
+}
+
+fn length_squared<F: Coordinate + Float, const D: usize>(vector: Vector<F, D>) -> F {
+	vector.iter().fold(F::zero(), |acc, &c| acc + c * c)
+}
+
+/// Every cell whose center may lie in `[min, max]`
+fn cell_bounds<F: Coordinate + Float, const D: usize>(
+	min: Point<F, D>,
+	max: Point<F, D>,
+) -> Aabb<i32, D> {
+	let cell = |c: F| {
+		c.floor()
+			.to_i32()
+			.expect("bounds out of the range of `i32`")
+	};
+
+	Aabb::new(min.map(cell), max.map(|c| cell(c) + 1))
+}
+
+/// Solid ball
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ball<F: Coordinate, const D: usize> {
+	pub center: Point<F, D>,
+	pub radius: F,
+}
+
+impl<F: Coordinate, const D: usize> Ball<F, D> {
+	pub fn new(center: Point<F, D>, radius: F) -> Self {
+		Self { center, radius }
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> Primitive<D> for Ball<F, D> {
+	fn bounds(&self) -> Aabb<i32, D> {
+		cell_bounds(
+			self.center.map(|c| c - self.radius),
+			self.center.map(|c| c + self.radius),
+		)
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		length_squared(center(position) - self.center) <= self.radius * self.radius
+	}
+}
+
+/// Hollow sphere, the shell reaches `thickness` inwards from `radius`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Sphere<F: Coordinate, const D: usize> {
+	pub center: Point<F, D>,
+	pub radius: F,
+	pub thickness: F,
+}
+
+impl<F: Coordinate, const D: usize> Sphere<F, D> {
+	pub fn new(center: Point<F, D>, radius: F, thickness: F) -> Self {
+		Self {
+			center,
+			radius,
+			thickness,
+		}
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> Primitive<D> for Sphere<F, D> {
+	fn bounds(&self) -> Aabb<i32, D> {
+		Ball::new(self.center, self.radius).bounds()
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		let distance = length_squared(center(position) - self.center);
+		let inner = (self.radius - self.thickness).max(F::zero());
+
+		inner * inner < distance && distance <= self.radius * self.radius
+	}
+}
+
+/// Solid axis aligned ellipsoid
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Ellipsoid<F: Coordinate, const D: usize> {
+	pub center: Point<F, D>,
+	pub radii: Vector<F, D>,
+}
+
+impl<F: Coordinate, const D: usize> Ellipsoid<F, D> {
+	pub fn new(center: Point<F, D>, radii: Vector<F, D>) -> Self {
+		Self { center, radii }
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> Primitive<D> for Ellipsoid<F, D> {
+	fn bounds(&self) -> Aabb<i32, D> {
+		cell_bounds(self.center - self.radii, self.center + self.radii)
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		length_squared((center(position) - self.center).component_div(&self.radii)) <= F::one()
+	}
+}
+
+/// Solid cylinder around the segment between `from` and `to` with flat ends
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cylinder<F: Coordinate, const D: usize> {
+	pub from: Point<F, D>,
+	pub to: Point<F, D>,
+	pub radius: F,
+}
+
+impl<F: Coordinate, const D: usize> Cylinder<F, D> {
+	pub fn new(from: Point<F, D>, to: Point<F, D>, radius: F) -> Self {
+		Self { from, to, radius }
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> Primitive<D> for Cylinder<F, D> {
+	fn bounds(&self) -> Aabb<i32, D> {
+		let min = self.from.coords.zip_map(&self.to.coords, F::min);
+		let max = self.from.coords.zip_map(&self.to.coords, F::max);
+
+		cell_bounds(
+			min.add_scalar(-self.radius).into(),
+			max.add_scalar(self.radius).into(),
+		)
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		let axis = self.to - self.from;
+		let offset = center(position) - self.from;
+
+		let t = offset.component_mul(&axis).sum() / length_squared(axis);
+
+		F::zero() <= t
+			&& t <= F::one()
+			&& length_squared(offset - axis * t) <= self.radius * self.radius
+	}
+}
+
+/// Solid simplex spanned by `D + 1` vertices, a triangle in 2D and a tetrahedron in 3D
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simplex<F: Coordinate, const D: usize> {
+	vertices: Vec<Point<F, D>>,
+	/// Maps an offset from the first vertex to its barycentric coordinates
+	inverse: Matrix<F, D, D>,
+}
+
+impl<F: Coordinate + Float, const D: usize> Simplex<F, D> {
+	/// Returns `None` unless there are exactly `D + 1` vertices which do not all lie in one hyperplane
+	pub fn new(vertices: impl IntoIterator<Item = Point<F, D>>) -> Option<Self> {
+		let vertices: Vec<_> = vertices.into_iter().collect();
+
+		if vertices.len() != D + 1 {
+			return None;
+		}
+
+		let edges = Matrix::from_fn(|row, column| vertices[column + 1][row] - vertices[0][row]);
+
+		Some(Self {
+			inverse: inverse(edges)?,
+			vertices,
+		})
+	}
+	pub fn vertices(&self) -> &[Point<F, D>] {
+		&self.vertices
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> Primitive<D> for Simplex<F, D> {
+	fn bounds(&self) -> Aabb<i32, D> {
+		let min = self.vertices.iter().fold(self.vertices[0], |acc, vertex| {
+			acc.coords.zip_map(&vertex.coords, F::min).into()
+		});
+		let max = self.vertices.iter().fold(self.vertices[0], |acc, vertex| {
+			acc.coords.zip_map(&vertex.coords, F::max).into()
+		});
+
+		cell_bounds(min, max)
+	}
+	fn contains(&self, position: Point<i32, D>) -> bool {
+		let barycentric = self.inverse * (center(position) - self.vertices[0]);
+
+		barycentric.iter().all(|&c| c >= F::zero())
+			&& barycentric.iter().fold(F::zero(), |acc, &c| acc + c) <= F::one()
+	}
+}
+
+/// Gauss-Jordan elimination with partial pivoting, `None` if `matrix` is singular
+fn inverse<F: Coordinate + Float, const D: usize>(
+	mut matrix: Matrix<F, D, D>,
+) -> Option<Matrix<F, D, D>> {
+	let mut inverse = Matrix::<F, D, D>::identity();
+
+	for column in 0..D {
+		let pivot = (column..D).max_by(|&a, &b| {
+			matrix[(a, column)]
+				.abs()
+				.partial_cmp(&matrix[(b, column)].abs())
+				.unwrap_or(std::cmp::Ordering::Equal)
+		})?;
+
+		if matrix[(pivot, column)].is_nan() || matrix[(pivot, column)].abs() <= F::epsilon() {
+			return None;
+		}
+
+		matrix.swap_rows(pivot, column);
+		inverse.swap_rows(pivot, column);
+
+		let scale = matrix[(column, column)];
+
+		for j in 0..D {
+			matrix[(column, j)] /= scale;
+			inverse[(column, j)] /= scale;
+		}
+
+		for row in (0..D).filter(|&row| row != column) {
+			let factor = matrix[(row, column)];
+
+			for j in 0..D {
+				matrix[(row, j)] = matrix[(row, j)] - factor * matrix[(column, j)];
+				inverse[(row, j)] = inverse[(row, j)] - factor * inverse[(column, j)];
+			}
+		}
+	}
+
+	Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_round() {
+		assert_eq!(
+			Ball::new(Point::from([0.0; 2]), 2.0).positions().count(),
+			12
+		);
+		assert_eq!(
+			Ball::new(Point::from([0.0; 4]), 1.0).positions().count(),
+			16
+		);
+		assert_eq!(
+			Sphere::new(Point::from([0.0; 2]), 2.0, 1.0)
+				.positions()
+				.count(),
+			8
+		);
+		assert_eq!(
+			Ellipsoid::new(Point::from([0.0; 2]), Vector::from([3.0, 1.0]))
+				.positions()
+				.count(),
+			12
+		);
+		assert_eq!(
+			Cylinder::new(Point::from([0.0; 3]), Point::from([0.0, 0.0, 4.0]), 1.0)
+				.positions()
+				.count(),
+			16
+		);
+	}
+
+	#[test]
+	fn test_simplex() {
+		let triangle = Simplex::new([[0.0, 0.0], [4.0, 0.0], [0.0, 4.0]].map(Point::from)).unwrap();
+
+		assert_eq!(triangle.positions().count(), 10);
+		assert!(triangle.contains(Point::from([1, 2])));
+		assert!(!triangle.contains(Point::from([2, 2])));
+
+		assert!(Simplex::new([[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]].map(Point::from)).is_none());
+		assert!(Simplex::new([[0.0, 0.0], [1.0, 1.0]].map(Point::from)).is_none());
+
+		let pentachoron = Simplex::new(
+			[
+				[0.0, 0.0, 0.0, 0.0],
+				[3.0, 0.0, 0.0, 0.0],
+				[0.0, 3.0, 0.0, 0.0],
+				[0.0, 0.0, 3.0, 0.0],
+				[0.0, 0.0, 0.0, 3.0],
+			]
+			.map(Point::from),
+		)
+		.unwrap();
+
+		// cells with a coordinate sum of at most `1`
+		assert_eq!(pentachoron.positions().count(), 5);
+	}
+}
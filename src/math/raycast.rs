@@ -0,0 +1,195 @@
+use super::*;
+
+use num::Float;
+
+/// A cell entered by a [`Traversal`]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Cell<F, const D: usize> {
+	pub position: Point<i32, D>,
+	/// Distance along the ray at which it enters this cell
+	pub distance: F,
+	/// Normal of the face through which the ray entered this cell, zero for the first cell
+	pub normal: Vector<i32, D>,
+}
+
+/// Amanatides–Woo traversal of every integer cell a ray passes through in `D` dimensions
+///
+/// Cell `p` covers `[p, p + 1)`, cells are yielded in order of increasing distance
+#[derive(Debug, Clone)]
+pub struct Traversal<F: Coordinate, const D: usize> {
+	position: Point<i32, D>,
+	step: Vector<i32, D>,
+	t_max: Vector<F, D>,
+	t_delta: Vector<F, D>,
+	distance: F,
+	max_distance: F,
+	first: bool,
+}
+
+impl<F: Coordinate + Float, const D: usize> Traversal<F, D> {
+	/// Traverses from `origin` towards `direction` until `max_distance`, which is measured in the units of the grid
+	///
+	/// A zero `direction` only visits the cell of `origin`
+	pub fn new(origin: Point<F, D>, direction: Vector<F, D>, max_distance: F) -> Self {
+		let length = direction
+			.iter()
+			.fold(F::zero(), |acc, &c| acc + c * c)
+			.sqrt();
+
+		let direction = if length > F::zero() {
+			direction.map(|c| c / length)
+		} else {
+			direction
+		};
+
+		let position = origin.map(|c| {
+			c.floor()
+				.to_i32()
+				.expect("`origin` out of the range of `i32`")
+		});
+
+		let mut step = Vector::zeros();
+		let mut t_max = Vector::repeat(F::infinity());
+		let mut t_delta = Vector::repeat(F::infinity());
+
+		for i in 0..D {
+			let cell = F::from(position[i]).unwrap_or_else(crate::lazy_unreachable!());
+
+			if direction[i] > F::zero() {
+				step[i] = 1;
+				t_max[i] = (cell + F::one() - origin[i]) / direction[i];
+				t_delta[i] = F::one() / direction[i];
+			} else if direction[i] < F::zero() {
+				step[i] = -1;
+				t_max[i] = (origin[i] - cell) / -direction[i];
+				t_delta[i] = F::one() / -direction[i];
+			}
+		}
+
+		Self {
+			position,
+			step,
+			t_max,
+			t_delta,
+			distance: F::zero(),
+			max_distance,
+			first: true,
+		}
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> Iterator for Traversal<F, D> {
+	type Item = Cell<F, D>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.first {
+			self.first = false;
+
+			return (self.max_distance >= F::zero()).then_some(Cell {
+				position: self.position,
+				distance: F::zero(),
+				normal: Vector::zeros(),
+			});
+		}
+
+		let axis = (0..D).reduce(|a, b| if self.t_max[b] < self.t_max[a] { b } else { a })?;
+
+		let distance = self.t_max[axis];
+
+		if !distance.is_finite() || distance > self.max_distance || distance < self.distance {
+			return None;
+		}
+
+		self.position[axis] += self.step[axis];
+		self.t_max[axis] = self.t_max[axis] + self.t_delta[axis];
+		self.distance = distance;
+
+		let mut normal = Vector::zeros();
+
+		normal[axis] = -self.step[axis];
+
+		Some(Cell {
+			position: self.position,
+			distance,
+			normal,
+		})
+	}
+}
+
+impl<F: Coordinate + Float, const D: usize> std::iter::FusedIterator for Traversal<F, D> {}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_axis_aligned() {
+		let cells: Vec<_> =
+			Traversal::new(Point::from([0.5, 0.5]), Vector::from([-2.0, 0.0]), 2.0).collect();
+
+		assert_eq!(
+			cells,
+			[
+				Cell {
+					position: Point::from([0, 0]),
+					distance: 0.0,
+					normal: Vector::from([0, 0]),
+				},
+				Cell {
+					position: Point::from([-1, 0]),
+					distance: 0.5,
+					normal: Vector::from([1, 0]),
+				},
+				Cell {
+					position: Point::from([-2, 0]),
+					distance: 1.5,
+					normal: Vector::from([1, 0]),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn test_diagonal() {
+		let cells: Vec<_> = Traversal::new(
+			Point::from([0.25, 0.5, 0.5, 0.5]),
+			Vector::from([1.0, 0.0, 1.0, 0.0]),
+			3.0,
+		)
+		.map(|cell| cell.position)
+		.collect();
+
+		assert_eq!(
+			cells,
+			[
+				[0, 0, 0, 0],
+				[0, 0, 1, 0],
+				[1, 0, 1, 0],
+				[1, 0, 2, 0],
+				[2, 0, 2, 0]
+			]
+			.map(Point::from)
+		);
+
+		let cells: Vec<_> =
+			Traversal::new(Point::from([0.5f64, 0.5]), Vector::from([3.0, -1.0]), 10.0).collect();
+
+		for pair in cells.windows(2) {
+			assert!(pair[0].distance <= pair[1].distance);
+			assert_eq!(
+				(pair[1].position - pair[0].position).abs().sum(),
+				1,
+				"cells must share a face"
+			);
+			assert_eq!(pair[0].position - pair[1].position, pair[1].normal);
+		}
+	}
+
+	#[test]
+	fn test_zero_direction() {
+		assert_eq!(
+			Traversal::new(Point::from([-0.5f32; 2]), Vector::zeros(), 10.0).count(),
+			1
+		);
+	}
+}
@@ -7,7 +7,7 @@ fn from_end_relative<S: Coordinate, const B: usize>(
 ) -> Point<S, B> {
 	let extents = matrix_cast(extents).unwrap();
 
-	(position + &extents)
+	(position + extents)
 		.coords
 		.zip_map(&extents, std::ops::Rem::rem)
 		.into()
@@ -34,9 +34,7 @@ pub fn world_to_block<S: Coordinate, const D: usize>(
 		.into();
 
 	// this position might be end-relative and if it is it should be converted
-	let block = from_end_relative(extents, block);
-
-	block
+	from_end_relative(extents, block)
 }
 
 #[inline(always)]
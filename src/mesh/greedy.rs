@@ -0,0 +1,303 @@
+use crate::chunk::ChunkRef;
+use crate::math::Aabb;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::ContiguousMemory;
+use crate::Shape;
+
+/// Direction a face of a block points in
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Face {
+	NegX,
+	PosX,
+	NegY,
+	PosY,
+	NegZ,
+	PosZ,
+}
+
+impl Face {
+	/// Every [`Face`] in the order of their discriminants
+	pub const ALL: [Self; 6] = [
+		Self::NegX,
+		Self::PosX,
+		Self::NegY,
+		Self::PosY,
+		Self::NegZ,
+		Self::PosZ,
+	];
+
+	/// Axis the face is perpendicular to
+	pub fn axis(self) -> usize {
+		self as usize / 2
+	}
+	pub fn is_positive(self) -> bool {
+		self as usize % 2 == 1
+	}
+	pub fn normal(self) -> Vector<i32, 3> {
+		let mut normal = Vector::zeros();
+
+		normal[self.axis()] = if self.is_positive() { 1 } else { -1 };
+
+		normal
+	}
+	pub fn opposite(self) -> Self {
+		Self::ALL[self as usize ^ 1]
+	}
+}
+
+/// Rectangle of merged block faces
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Quad<F> {
+	/// Minimum corner of the rectangle in `Chunk` local coordinates, the faces of block `p` lie on the boundary of `[p, p + 1]`
+	pub position: Point<i32, 3>,
+	/// Extents of the rectangle, `0` along the axis of `face`
+	pub size: Vector<i32, 3>,
+	pub face: Face,
+	/// Value every merged face had
+	pub item: F,
+}
+
+/// Builds greedy merged [`Quad`]s out of the visible faces of `chunk`
+///
+/// * `neighbors`: the `Chunk`s next to `chunk` indexed by [`Face`], faces on the border to a missing neighbor are visible
+/// * `opaque`: whether a block hides the faces of the blocks next to it
+/// * `face`: value of a face of a block, `None` if it has no face in that direction.
+///   Adjacent faces with equal values are merged
+///
+/// Quads are ordered by [`Face`], then by their position along its axis
+pub fn greedy_mesh<T, S, F>(
+	chunk: ChunkRef<'_, T, S, 3>,
+	neighbors: [Option<ChunkRef<'_, T, S, 3>>; 6],
+	mut opaque: impl FnMut(&T::Item) -> bool,
+	mut face: impl FnMut(&T::Item, Face) -> Option<F>,
+) -> Vec<Quad<F>>
+where
+	T: ?Sized + ContiguousMemory,
+	S: Shape<3>,
+	F: PartialEq,
+{
+	let bounds = chunk.shape.bounds();
+	let extents = bounds.extents();
+
+	let mut quads = Vec::new();
+
+	for direction in Face::ALL {
+		let axis = direction.axis();
+		let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+
+		let mut mask: Vec<Option<F>> = Vec::with_capacity(extents[u] * extents[v]);
+
+		for layer in 0..extents[axis] {
+			mask.clear();
+
+			for j in 0..extents[v] {
+				for i in 0..extents[u] {
+					let mut offset = Vector::zeros();
+
+					offset[axis] = layer as i32;
+					offset[u] = i as i32;
+					offset[v] = j as i32;
+
+					let position = bounds.min + offset;
+
+					let value = chunk
+						.block(position)
+						.and_then(|block| face(block, direction));
+
+					let hidden = value.is_some()
+						&& neighbor(&chunk, &neighbors, bounds, position, direction)
+							.is_some_and(&mut opaque);
+
+					mask.push(if hidden { None } else { value });
+				}
+			}
+
+			for j in 0..extents[v] {
+				for i in 0..extents[u] {
+					let Some(item) = mask[i + j * extents[u]].take() else {
+						continue;
+					};
+
+					let same = |mask: &[Option<F>], i: usize, j: usize| {
+						mask[i + j * extents[u]].as_ref() == Some(&item)
+					};
+
+					let mut width = 1;
+
+					while i + width < extents[u] && same(&mask, i + width, j) {
+						width += 1;
+					}
+
+					let mut height = 1;
+
+					while j + height < extents[v]
+						&& (i..i + width).all(|i| same(&mask, i, j + height))
+					{
+						height += 1;
+					}
+
+					for j in j..j + height {
+						for i in i..i + width {
+							mask[i + j * extents[u]] = None;
+						}
+					}
+
+					let mut position = bounds.min;
+					let mut size = Vector::zeros();
+
+					position[axis] += layer as i32 + direction.is_positive() as i32;
+					position[u] += i as i32;
+					position[v] += j as i32;
+					size[u] = width as i32;
+					size[v] = height as i32;
+
+					quads.push(Quad {
+						position,
+						size,
+						face: direction,
+						item,
+					});
+				}
+			}
+		}
+	}
+
+	quads
+}
+
+/// Block next to `position` in `direction`, looked up in the neighboring `Chunk` on the border
+fn neighbor<'a, T, S>(
+	chunk: &ChunkRef<'a, T, S, 3>,
+	neighbors: &[Option<ChunkRef<'a, T, S, 3>>; 6],
+	bounds: Aabb<i32, 3>,
+	position: Point<i32, 3>,
+	direction: Face,
+) -> Option<&'a T::Item>
+where
+	T: ?Sized + ContiguousMemory,
+	S: Shape<3>,
+{
+	let mut next = position + direction.normal();
+
+	if bounds.contains(next) {
+		let index = chunk.shape.position_to_index(next)?;
+
+		return chunk.storage.as_slice().get(index);
+	}
+
+	let neighbor = neighbors[direction as usize].as_ref()?;
+
+	let axis = direction.axis();
+
+	next[axis] = if direction.is_positive() {
+		bounds.min[axis]
+	} else {
+		bounds.max[axis] - 1
+	};
+
+	let index = neighbor.shape.position_to_index(next)?;
+
+	neighbor.storage.as_slice().get(index)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::Chunk;
+
+	type Chunk3 = Chunk<Vec<u8>, ct::Uniform<4, 3>, 3>;
+
+	fn mesh(chunk: &Chunk3, neighbors: [Option<&Chunk3>; 6]) -> Vec<Quad<u8>> {
+		greedy_mesh(
+			chunk.as_ref(),
+			neighbors.map(|neighbor| neighbor.map(Chunk::as_ref)),
+			|&block| block != 0,
+			|&block, _| (block != 0).then_some(block),
+		)
+	}
+
+	#[test]
+	fn test_single_block() {
+		let chunk = Chunk3::from_position(|position| (position == Point::from([1, 2, 3])) as u8);
+
+		let quads = mesh(&chunk, [None; 6]);
+
+		assert_eq!(quads.len(), 6);
+
+		for quad in quads {
+			assert_eq!(quad.size.sum(), 2);
+			assert_eq!(quad.item, 1);
+		}
+	}
+
+	#[test]
+	fn test_merge() {
+		// a 4 x 4 x 2 slab with a 2 x 2 x 1 patch of a different block on top
+		let chunk = Chunk3::from_position(|position| match position.z {
+			0 => 1,
+			1 if position.x < 2 && position.y < 2 => 2,
+			1 => 1,
+			_ => 0,
+		});
+
+		let quads = mesh(&chunk, [None; 6]);
+
+		let top: Vec<_> = quads
+			.iter()
+			.filter(|quad| quad.face == Face::PosZ)
+			.collect();
+
+		assert_eq!(top.len(), 3);
+		assert!(top.iter().all(|quad| quad.position.z == 2));
+		assert_eq!(
+			top.iter()
+				.map(|quad| quad.size.x * quad.size.y)
+				.sum::<i32>(),
+			16
+		);
+
+		let bottom: Vec<_> = quads
+			.iter()
+			.filter(|quad| quad.face == Face::NegZ)
+			.collect();
+
+		assert_eq!(bottom.len(), 1);
+		assert_eq!(bottom[0].position, Point::from([0, 0, 0]));
+		assert_eq!(bottom[0].size, Vector::from([4, 4, 0]));
+
+		// the 2 x 2 patch splits two of the sides
+		assert_eq!(quads.len() - top.len() - bottom.len(), 3 + 1 + 3 + 1);
+	}
+
+	#[test]
+	fn test_neighbors() {
+		let full = Chunk3::from_position(|_| 1);
+		let empty = Chunk3::from_position(|_| 0);
+
+		assert_eq!(mesh(&full, [None; 6]).len(), 6);
+
+		let mut neighbors = [None; 6];
+
+		neighbors[Face::PosZ as usize] = Some(&full);
+		neighbors[Face::NegX as usize] = Some(&empty);
+
+		let quads = mesh(&full, neighbors);
+
+		assert_eq!(quads.len(), 5);
+		assert!(quads.iter().all(|quad| quad.face != Face::PosZ));
+	}
+
+	#[test]
+	fn test_face() {
+		for face in Face::ALL {
+			assert_eq!(face.opposite().opposite(), face);
+			assert_eq!(face.normal(), -face.opposite().normal());
+		}
+	}
+}
@@ -0,0 +1,335 @@
+use crate::math::Aabb;
+use crate::math::Coordinate;
+use crate::math::Point;
+use crate::math::Vector;
+
+use num::Float;
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Indexed triangle mesh, triangles are counter clockwise when looking at them from outside
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh<F: Coordinate> {
+	pub vertices: Vec<Point<F, 3>>,
+	/// Three indices into `vertices` per triangle
+	pub indices: Vec<u32>,
+}
+
+impl<F: Coordinate> Mesh<F> {
+	pub fn triangles(&self) -> impl '_ + Iterator<Item = [Point<F, 3>; 3]> {
+		self.indices
+			.chunks_exact(3)
+			.map(|triangle| [0, 1, 2].map(|i| self.vertices[triangle[i] as usize]))
+	}
+}
+
+/// Corner `c` of a cube is at `(c & 1, c >> 1 & 1, c >> 2 & 1)`
+fn corner(c: usize) -> Vector<i32, 3> {
+	Vector::from([c & 1, c >> 1 & 1, c >> 2 & 1].map(|c| c as i32))
+}
+
+/// Edge `e` runs along axis `e / 4`, returns the corners at its ends
+fn edge_corners(e: usize) -> (usize, usize) {
+	let axis = e / 4;
+	let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+	let start = (e & 1) << u | (e >> 1 & 1) << v;
+
+	(start, start | 1 << axis)
+}
+
+/// Edge between two corners which differ along one axis
+fn edge_between(a: usize, b: usize) -> usize {
+	let axis = (a ^ b).trailing_zeros() as usize;
+	let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+	let start = a & b;
+
+	axis * 4 + (start >> u & 1) + 2 * (start >> v & 1)
+}
+
+/// Faces of the cube touching edge `e`, as `(axis, side)`
+fn edge_faces(e: usize) -> [(usize, usize); 2] {
+	let axis = e / 4;
+
+	[((axis + 1) % 3, e & 1), ((axis + 2) % 3, e >> 1 & 1)]
+}
+
+/// Polygon of the surface in a cube, as the cube edges its vertices lie on
+#[derive(Debug)]
+struct Polygon {
+	edges: Vec<u8>,
+	/// Whether fanning out from the first vertex is safe, otherwise a vertex is added in the center
+	///
+	/// A fan is unsafe if one of its diagonals lies in a face of the cube, the cube on the other side of that face
+	/// would produce the same diagonal and the mesh would no longer be manifold
+	fan: bool,
+}
+
+/// Polygons, as edges of the cube, separating the inside corners which are the set bits of `case`
+///
+/// On every face of the cube a segment is cut around each run of inside corners, going counter clockwise when
+/// looking at the face from outside the cube. Diagonal inside corners are thus always separated,
+/// which only depends on the face and keeps neighboring cubes consistent. The segments are chained into polygons
+fn polygons(case: usize) -> Vec<Polygon> {
+	let inside = |c: usize| case >> c & 1 == 1;
+
+	let mut next = [None; 12];
+
+	for axis in 0..3 {
+		let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+
+		for side in 0..2 {
+			let mut cycle =
+				[(0, 0), (1, 0), (1, 1), (0, 1)].map(|(a, b)| side << axis | a << u | b << v);
+
+			// counter clockwise from the side the face is looked at
+			if side == 0 {
+				cycle.reverse();
+			}
+
+			let crossing = |i: usize| edge_between(cycle[i % 4], cycle[(i + 1) % 4]);
+
+			for i in 0..4 {
+				if !inside(cycle[i]) && inside(cycle[(i + 1) % 4]) {
+					let end = (i + 1..i + 4)
+						.find(|&j| inside(cycle[j % 4]) && !inside(cycle[(j + 1) % 4]))
+						.unwrap_or_else(crate::lazy_unreachable!());
+
+					next[crossing(i)] = Some(crossing(end));
+				}
+			}
+		}
+	}
+
+	let mut polygons = Vec::new();
+	let mut visited = [false; 12];
+
+	for start in 0..12 {
+		if visited[start] || next[start].is_none() {
+			continue;
+		}
+
+		let mut edges = vec![start];
+
+		visited[start] = true;
+
+		while let Some(edge) = next[*edges.last().unwrap_or_else(crate::lazy_unreachable!())] {
+			if edge == start {
+				break;
+			}
+
+			visited[edge] = true;
+			edges.push(edge);
+		}
+
+		let share_face = |a: usize, b: usize| {
+			edge_faces(a)
+				.iter()
+				.any(|face| edge_faces(b).contains(face))
+		};
+
+		let safe = (0..edges.len()).find(|&first| {
+			(2..edges.len() - 1)
+				.all(|k| !share_face(edges[first], edges[(first + k) % edges.len()]))
+		});
+
+		if let Some(first) = safe {
+			edges.rotate_left(first);
+		}
+
+		polygons.push(Polygon {
+			edges: edges.into_iter().map(|edge| edge as u8).collect(),
+			fan: safe.is_some(),
+		});
+	}
+
+	polygons
+}
+
+fn cases() -> &'static [Vec<Polygon>] {
+	static CASES: OnceLock<Vec<Vec<Polygon>>> = OnceLock::new();
+
+	CASES.get_or_init(|| (0..256).map(polygons).collect())
+}
+
+/// Extracts the isosurface at `iso` of the scalar field `sample` with marching cubes
+///
+/// Every cube whose minimum corner is in `region` is polygonized, `sample` is called for the corners of each cube.
+/// Points with a value below `iso` are inside of the surface, as in a signed distance field.
+/// Cubes with a corner for which `sample` returns `None` are skipped
+///
+/// Vertices on the same cube edge are shared, so meshes of adjacent regions line up exactly
+pub fn marching_cubes<F: Coordinate + Float>(
+	region: Aabb<i32, 3>,
+	iso: F,
+	mut sample: impl FnMut(Point<i32, 3>) -> Option<F>,
+) -> Mesh<F> {
+	let cases = cases();
+
+	let mut mesh = Mesh {
+		vertices: Vec::new(),
+		indices: Vec::new(),
+	};
+	let mut vertices = HashMap::new();
+
+	for cube in region.points() {
+		let mut corners = [F::zero(); 8];
+
+		let complete = (0..8).all(|c| match sample(cube + corner(c)) {
+			Some(value) => {
+				corners[c] = value;
+
+				true
+			}
+			None => false,
+		});
+
+		if !complete {
+			continue;
+		}
+
+		let case = (0..8).fold(0, |case, c| case | ((corners[c] < iso) as usize) << c);
+
+		for polygon in &cases[case] {
+			let indices: Vec<u32> = polygon
+				.edges
+				.iter()
+				.map(|&edge| {
+					let (a, b) = edge_corners(edge as usize);
+					let axis = edge as usize / 4;
+
+					*vertices.entry((cube + corner(a), axis)).or_insert_with(|| {
+						let t = (iso - corners[a]) / (corners[b] - corners[a]);
+
+						let mut vertex = (cube + corner(a))
+							.map(|c| F::from(c).unwrap_or_else(crate::lazy_unreachable!()));
+
+						vertex[axis] += t;
+
+						mesh.vertices.push(vertex);
+
+						mesh.vertices.len() as u32 - 1
+					})
+				})
+				.collect();
+
+			if polygon.fan {
+				for pair in indices[1..].windows(2) {
+					mesh.indices.extend([indices[0], pair[0], pair[1]]);
+				}
+			} else {
+				let count = F::from(indices.len()).unwrap_or_else(crate::lazy_unreachable!());
+				let center = indices.iter().fold(Vector::zeros(), |acc, &index| {
+					acc + mesh.vertices[index as usize].coords
+				}) / count;
+
+				mesh.vertices.push(center.into());
+
+				let center = mesh.vertices.len() as u32 - 1;
+
+				for i in 0..indices.len() {
+					mesh.indices
+						.extend([center, indices[i], indices[(i + 1) % indices.len()]]);
+				}
+			}
+		}
+	}
+
+	mesh
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Every edge of a closed, consistently oriented mesh is used once in each direction
+	fn assert_closed(mesh: &Mesh<f64>) {
+		let mut edges = HashMap::new();
+
+		for triangle in mesh.indices.chunks_exact(3) {
+			for i in 0..3 {
+				*edges
+					.entry((triangle[i], triangle[(i + 1) % 3]))
+					.or_insert(0) += 1;
+			}
+		}
+
+		for (&(a, b), &count) in &edges {
+			assert_eq!(count, 1);
+			assert_eq!(edges.get(&(b, a)), Some(&1));
+		}
+	}
+
+	fn volume(mesh: &Mesh<f64>) -> f64 {
+		mesh.triangles()
+			.map(|[a, b, c]| a.coords.dot(&b.coords.cross(&c.coords)) / 6.0)
+			.sum()
+	}
+
+	#[test]
+	fn test_cases() {
+		for case in 0..256 {
+			let polygons = polygons(case);
+
+			assert_eq!(polygons.is_empty(), case == 0 || case == 255);
+
+			// every vertex is on an edge between an inside and an outside corner
+			for &edge in polygons.iter().flat_map(|polygon| &polygon.edges) {
+				let (a, b) = edge_corners(edge as usize);
+
+				assert_ne!(case >> a & 1, case >> b & 1);
+			}
+		}
+	}
+
+	#[test]
+	fn test_noise() {
+		let region = Aabb::new(Point::from([0; 3]), Point::from([8; 3]));
+
+		// outside on the border of the lattice so that the surface is closed
+		let mesh = marching_cubes(region, 0.0, |position| {
+			if position.iter().any(|&c| c == 0 || c == 8) {
+				return Some(1.0);
+			}
+
+			let hash = ((position.x * 73856093) ^ (position.y * 19349663) ^ (position.z * 83492791))
+				as u32;
+
+			Some((hash % 1000) as f64 / 500.0 - 1.0)
+		});
+
+		assert!(!mesh.indices.is_empty());
+		assert_closed(&mesh);
+		assert!(volume(&mesh) > 0.0);
+	}
+
+	#[test]
+	fn test_sphere() {
+		let center = Vector::from([0.3, -0.2, 0.1]);
+		let radius = 5.0;
+
+		let sphere = |position: Point<i32, 3>| {
+			Some((position.map(f64::from).coords - center).norm() - radius)
+		};
+
+		let region = Aabb::new(Point::from([-7; 3]), Point::from([7; 3]));
+		let mesh = marching_cubes(region, 0.0, sphere);
+
+		assert_closed(&mesh);
+
+		for vertex in &mesh.vertices {
+			assert!(((vertex.coords - center).norm() - radius).abs() < 0.1);
+		}
+
+		let expected = 4.0 / 3.0 * std::f64::consts::PI * radius.powi(3);
+
+		assert!((volume(&mesh) - expected).abs() < expected * 0.05);
+
+		// a field which is missing on one side leaves an open mesh
+		let half = marching_cubes(region, 0.0, |position| {
+			sphere(position).filter(|_| position.z < 1)
+		});
+
+		assert!(half.indices.len() < mesh.indices.len());
+	}
+}
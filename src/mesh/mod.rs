@@ -0,0 +1,10 @@
+//! Mesh extraction out of 3 dimensional `Chunk`s
+
+mod greedy;
+mod marching_cubes;
+
+pub use greedy::greedy_mesh;
+pub use greedy::Face;
+pub use greedy::Quad;
+pub use marching_cubes::marching_cubes;
+pub use marching_cubes::Mesh;
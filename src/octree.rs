@@ -0,0 +1,448 @@
+//! Sparse voxel octrees, or `2^D`-trees in `D` dimensions
+
+use crate::chunk::Chunk;
+use crate::math::raycast::Cell;
+use crate::math::Aabb;
+use crate::math::Coordinate;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::Shape;
+
+use num::Float;
+
+/// Node of an [`Octree`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+enum Node<I> {
+	/// Every block of the node has the same value, `None` if they are empty
+	Leaf(Option<I>),
+	/// Index of the first of the `2^D` children in `nodes`
+	Branch(u32),
+}
+
+/// Offset of child `child` of a node, in units of half of its size
+fn corner<const D: usize>(child: usize) -> Vector<i32, D> {
+	Vector::from_fn(|axis, _| (child >> axis & 1) as i32)
+}
+
+/// Distance along the ray at which it enters `aabb` and the normal of the face it enters through, zero if `origin`
+/// is inside of `aabb`. `None` if it misses `aabb` or enters it after `max_distance`
+fn enter<F: Coordinate + Float, const D: usize>(
+	origin: Point<F, D>,
+	direction: Vector<F, D>,
+	max_distance: F,
+	aabb: Aabb<i32, D>,
+) -> Option<(F, Vector<i32, D>)> {
+	let mut near = F::zero();
+	let mut far = max_distance;
+	let mut normal = Vector::zeros();
+
+	for axis in 0..D {
+		let min = F::from(aabb.min[axis]).unwrap_or_else(crate::lazy_unreachable!());
+		let max = F::from(aabb.max[axis]).unwrap_or_else(crate::lazy_unreachable!());
+
+		if direction[axis] == F::zero() {
+			if origin[axis] < min || origin[axis] >= max {
+				return None;
+			}
+
+			continue;
+		}
+
+		let (t0, t1, sign) = if direction[axis] > F::zero() {
+			(min, max, -1)
+		} else {
+			(max, min, 1)
+		};
+		let (t0, t1) = (
+			(t0 - origin[axis]) / direction[axis],
+			(t1 - origin[axis]) / direction[axis],
+		);
+
+		if t0 > near {
+			near = t0;
+			normal = Vector::zeros();
+			normal[axis] = sign;
+		}
+
+		far = far.min(t1);
+	}
+
+	(near < far).then_some((near, normal))
+}
+
+/// Read only tree over a cube of `2^depth` blocks per axis in which regions of equal blocks are merged
+///
+/// Child `i` of a node covers the half of it which is offset along axis `a` if bit `a` of `i` is set
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Octree<I, const D: usize> {
+	root: Node<I>,
+	nodes: Vec<Node<I>>,
+	min: Point<i32, D>,
+	depth: u32,
+}
+
+impl<I: Clone + PartialEq, const D: usize> Octree<I, D> {
+	/// Node covering `size` blocks per axis from `min`, `uniform` may return the value of every block in a cube to
+	/// skip sampling it
+	fn build(
+		nodes: &mut Vec<Node<I>>,
+		min: Point<i32, D>,
+		size: i32,
+		uniform: &mut impl FnMut(Aabb<i32, D>) -> Option<Option<I>>,
+		sample: &mut impl FnMut(Point<i32, D>) -> Option<I>,
+	) -> Node<I> {
+		if size == 1 {
+			return Node::Leaf(sample(min));
+		}
+
+		if let Some(value) = uniform(Aabb::new(min, min + Vector::repeat(size))) {
+			return Node::Leaf(value);
+		}
+
+		let half = size / 2;
+		let children: Vec<_> = (0..1 << D)
+			.map(|child| Self::build(nodes, min + corner(child) * half, half, uniform, sample))
+			.collect();
+
+		if let Node::Leaf(first) = &children[0] {
+			if children
+				.iter()
+				.all(|child| matches!(child, Node::Leaf(value) if value == first))
+			{
+				return Node::Leaf(first.clone());
+			}
+		}
+
+		let first = nodes.len() as u32;
+
+		nodes.extend(children);
+
+		Node::Branch(first)
+	}
+	pub(crate) fn from_fn_uniform(
+		min: Point<i32, D>,
+		depth: u32,
+		mut uniform: impl FnMut(Aabb<i32, D>) -> Option<Option<I>>,
+		mut sample: impl FnMut(Point<i32, D>) -> Option<I>,
+	) -> Self {
+		assert!(depth <= Self::MAX_DEPTH, "`depth` is too large");
+		assert!(
+			min.iter().all(|c| c.checked_add(1 << depth).is_some()),
+			"the cube does not fit into `i32` coordinates"
+		);
+
+		let mut nodes = Vec::new();
+		let root = Self::build(&mut nodes, min, 1 << depth, &mut uniform, &mut sample);
+
+		Self {
+			root,
+			nodes,
+			min,
+			depth,
+		}
+	}
+	/// Tree over the cube of `2^depth` blocks per axis from `min` holding `sample(position)`, `None` for empty blocks
+	///
+	/// # Panics
+	/// This function panics if `depth > Self::MAX_DEPTH` or if the cube does not fit into `i32` coordinates
+	pub fn from_fn(
+		min: Point<i32, D>,
+		depth: u32,
+		sample: impl FnMut(Point<i32, D>) -> Option<I>,
+	) -> Self {
+		Self::from_fn_uniform(min, depth, |_| None, sample)
+	}
+	/// Tree over the smallest cube containing the blocks of `chunk`, blocks outside of `chunk` are empty
+	pub fn from_chunk<T, S>(chunk: &Chunk<T, S, D>) -> Self
+	where
+		T: ContiguousMemory<Item = I>,
+		S: Shape<D>,
+	{
+		let bounds = chunk.shape.bounds();
+
+		Self::from_fn(bounds.min, depth(bounds), |position| {
+			chunk.block(position).cloned()
+		})
+	}
+}
+
+/// Smallest depth of a tree containing `bounds`
+pub(crate) fn depth<const D: usize>(bounds: Aabb<i32, D>) -> u32 {
+	let size = bounds.extents().max() as u64;
+
+	size.next_power_of_two().trailing_zeros()
+}
+
+impl<I, const D: usize> Octree<I, D> {
+	/// Largest depth, a cube of `2^31` blocks per axis does not fit into `i32` coordinates
+	pub const MAX_DEPTH: u32 = 30;
+	/// The cube covered by the tree
+	pub fn bounds(&self) -> Aabb<i32, D> {
+		Aabb::new(self.min, self.min + Vector::repeat(1 << self.depth))
+	}
+	pub fn depth(&self) -> u32 {
+		self.depth
+	}
+	/// Number of nodes, including the root
+	pub fn node_count(&self) -> usize {
+		self.nodes.len() + 1
+	}
+	/// The block at `position`, `None` if it is empty or outside of the tree
+	pub fn get(&self, position: Point<i32, D>) -> Option<&I> {
+		if !self.bounds().contains(position) {
+			return None;
+		}
+
+		let mut node = &self.root;
+		let mut min = self.min;
+		let mut size = 1 << self.depth;
+
+		loop {
+			match *node {
+				Node::Leaf(ref value) => return value.as_ref(),
+				Node::Branch(first) => {
+					size /= 2;
+
+					let child = (0..D).fold(0, |child, axis| {
+						child | ((position[axis] - min[axis] >= size) as usize) << axis
+					});
+
+					min += corner(child) * size;
+					node = &self.nodes[first as usize + child];
+				}
+			}
+		}
+	}
+	/// Every leaf which is not empty, as the cube it covers and the value of its blocks
+	pub fn leaves(&self) -> impl '_ + Iterator<Item = (Aabb<i32, D>, &I)> {
+		let mut stack = vec![(&self.root, self.min, 1 << self.depth)];
+
+		std::iter::from_fn(move || loop {
+			let (node, min, size) = stack.pop()?;
+
+			match *node {
+				Node::Leaf(ref value) => {
+					if let Some(value) = value {
+						return Some((Aabb::new(min, min + Vector::repeat(size)), value));
+					}
+				}
+				Node::Branch(first) => {
+					let half = size / 2;
+
+					stack.extend((0..1 << D).rev().map(|child| {
+						(
+							&self.nodes[first as usize + child],
+							min + corner(child) * half,
+							half,
+						)
+					}));
+				}
+			}
+		})
+	}
+	fn raycast_node<'a, F: Coordinate + Float>(
+		&'a self,
+		node: &'a Node<I>,
+		aabb: Aabb<i32, D>,
+		ray: (Point<F, D>, Vector<F, D>, F),
+		predicate: &mut impl FnMut(&I) -> bool,
+	) -> Option<(Cell<F, D>, &'a I)> {
+		let (origin, direction, max_distance) = ray;
+
+		match *node {
+			Node::Leaf(ref value) => {
+				let (distance, normal) = enter(origin, direction, max_distance, aabb)?;
+				let value = value.as_ref().filter(|&value| predicate(value))?;
+
+				let position = Point::from(Vector::from_fn(|axis, _| {
+					let c = (origin[axis] + direction[axis] * distance)
+						.floor()
+						.to_i32()
+						.unwrap_or(aabb.min[axis]);
+
+					c.clamp(aabb.min[axis], aabb.max[axis] - 1)
+				}));
+
+				let cell = Cell {
+					position,
+					distance,
+					normal,
+				};
+
+				Some((cell, value))
+			}
+			Node::Branch(first) => {
+				let half = (aabb.max[0] - aabb.min[0]) / 2;
+
+				let mut children: Vec<_> = (0..1 << D)
+					.filter_map(|child| {
+						let min = aabb.min + corner(child) * half;
+						let aabb = Aabb::new(min, min + Vector::repeat(half));
+						let (distance, _) = enter(origin, direction, max_distance, aabb)?;
+
+						Some((distance, child, aabb))
+					})
+					.collect();
+
+				children.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+				children.into_iter().find_map(|(_, child, aabb)| {
+					self.raycast_node(&self.nodes[first as usize + child], aabb, ray, predicate)
+				})
+			}
+		}
+	}
+	/// The first block along the ray from `origin` towards `direction` for which `predicate` returns `true`
+	///
+	/// Like [`Multiform::raycast`](crate::world::Multiform::raycast), but skips merged regions at once.
+	/// Within a merged region the ray hits the block through which it enters the region
+	pub fn raycast<F: Coordinate + Float>(
+		&self,
+		origin: Point<F, D>,
+		direction: Vector<F, D>,
+		max_distance: F,
+		mut predicate: impl FnMut(&I) -> bool,
+	) -> Option<(Cell<F, D>, &I)> {
+		let length = direction
+			.iter()
+			.fold(F::zero(), |acc, &c| acc + c * c)
+			.sqrt();
+
+		let direction = if length > F::zero() {
+			direction.map(|c| c / length)
+		} else {
+			direction
+		};
+
+		self.raycast_node(
+			&self.root,
+			self.bounds(),
+			(origin, direction, max_distance),
+			&mut predicate,
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::Traversal;
+
+	fn ball(position: Point<i32, 3>) -> Option<u8> {
+		let offset = position.map(|c| c as f64 + 0.5).coords - Vector::from([1.0, -0.5, 0.25]);
+
+		(offset.norm() < 5.5).then_some(if position.y < 0 { 1 } else { 2 })
+	}
+
+	#[test]
+	fn test_from_fn() {
+		let octree = Octree::from_fn(Point::from([-8; 3]), 4, ball);
+
+		assert_eq!(
+			octree.bounds(),
+			Aabb::new(Point::from([-8; 3]), Point::from([8; 3]))
+		);
+		assert!(octree.node_count() < 16 * 16 * 16 / 4);
+
+		let bounds = octree.bounds().expand(Vector::repeat(2));
+
+		for position in bounds.points() {
+			let expected = ball(position).filter(|_| octree.bounds().contains(position));
+
+			assert_eq!(octree.get(position).copied(), expected);
+		}
+
+		let volume: i32 = octree.leaves().map(|(aabb, _)| aabb.volume()).sum();
+
+		assert_eq!(
+			volume as usize,
+			octree.bounds().points().filter_map(ball).count()
+		);
+
+		// uniform regions collapse into a single leaf
+		let uniform = Octree::from_fn(Point::from([0; 2]), 10, |_| Some(3));
+
+		assert_eq!(uniform.node_count(), 1);
+		assert_eq!(uniform.get(Point::from([1000, 3])), Some(&3));
+		assert_eq!(uniform.get(Point::from([1024, 3])), None);
+	}
+
+	#[test]
+	fn test_raycast() {
+		let octree = Octree::from_fn(Point::from([-8; 3]), 4, ball);
+
+		let rays = [
+			([-7.3, 0.37, 0.61], [1.0, 0.02, 0.01]),
+			([7.9, 7.9, 7.9], [-1.0, -1.1, -0.9]),
+			([1.3, 0.2, -7.7], [0.1, -0.05, 1.0]),
+			([1.5, 0.5, 0.5], [0.0, 1.0, 0.0]),
+			([-7.5, 7.5, 7.5], [0.0, 0.0, -1.0]),
+		];
+
+		for (origin, direction) in rays {
+			let origin = Point::from(origin);
+			let direction = Vector::from(direction);
+
+			let expected = Traversal::new(origin, direction, 32.0)
+				.find_map(|cell| Some((cell, *octree.get(cell.position)?)));
+			let hit = octree
+				.raycast(origin, direction, 32.0, |_| true)
+				.map(|(cell, &value)| (cell, value));
+
+			match (hit, expected) {
+				(Some((hit, a)), Some((expected, b))) => {
+					assert_eq!(hit.position, expected.position);
+					assert_eq!(hit.normal, expected.normal);
+					assert!((hit.distance - expected.distance).abs() < 1e-9);
+					assert_eq!(a, b);
+				}
+				(hit, expected) => assert_eq!(hit.is_some(), expected.is_some()),
+			}
+		}
+
+		// the predicate skips whole leaves
+		let (cell, &value) = octree
+			.raycast(
+				Point::from([1.5, -7.5, 0.5]),
+				Vector::from([0.0, 1.0, 0.0]),
+				32.0,
+				|&value| value == 2,
+			)
+			.unwrap();
+
+		assert_eq!(value, 2);
+		assert_eq!(cell.position, Point::from([1, 0, 0]));
+		assert_eq!(cell.normal, Vector::from([0, -1, 0]));
+	}
+
+	#[test]
+	fn test_from_chunk() {
+		let chunk = Chunk::<Vec<u8>, ct::Uniform<3, 3>, 3>::from_position(|position| {
+			(position.x == 2) as u8
+		});
+
+		let octree = Octree::from_chunk(&chunk);
+
+		assert_eq!(octree.depth(), 2);
+
+		for position in octree.bounds().points() {
+			assert_eq!(octree.get(position), chunk.block(position));
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn test_max_depth() {
+		Octree::<u8, 2>::from_fn(Point::origin(), Octree::<u8, 2>::MAX_DEPTH + 1, |_| None);
+	}
+}
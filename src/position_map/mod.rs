@@ -7,6 +7,7 @@ pub use entry::OccupiedEntry;
 pub use entry::VacantEntry;
 pub use ordered_point::OrderedPoint;
 
+use crate::math::Aabb;
 use crate::math::Point;
 
 use std::collections::BTreeMap;
@@ -28,6 +29,9 @@ impl<T, const D: usize> PositionMap<T, D> {
 	pub fn len(&self) -> usize {
 		self.inner.len()
 	}
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
 	pub fn positions(&self) -> impl '_ + Iterator<Item = Point<i32, D>> {
 		self.inner.keys().map(|a| a.coordinates)
 	}
@@ -37,6 +41,7 @@ impl<T, const D: usize> PositionMap<T, D> {
 	pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<i32, D>, &mut T)> {
 		self.inner.iter_mut().map(|(a, b)| (a.coordinates, b))
 	}
+	#[allow(clippy::should_implement_trait)]
 	pub fn into_iter(self) -> impl Iterator<Item = (Point<i32, D>, T)> {
 		self.inner.into_iter().map(|(a, b)| (a.coordinates, b))
 	}
@@ -59,11 +64,113 @@ impl<T, const D: usize> PositionMap<T, D> {
 	pub fn insert(&mut self, position: Point<i32, D>, chunk: T) -> Option<T> {
 		self.inner.insert(OrderedPoint::new(position), chunk)
 	}
-	pub fn entry(&mut self, position: Point<i32, D>) -> entry::Entry<T, D> {
+	pub fn entry(&mut self, position: Point<i32, D>) -> entry::Entry<'_, T, D> {
 		let entry = self.inner.entry(OrderedPoint::new(position));
 
 		entry::Entry::from(entry)
 	}
+	/// Mutable references to the values at every position in `positions`
+	///
+	/// Walks the values from the first to the last of the `positions` in the order of [`OrderedPoint`].
+	/// Returns `None` if any of the `positions` is missing or if they are not distinct
+	pub fn get_many_mut<const N: usize>(
+		&mut self,
+		positions: [Point<i32, D>; N],
+	) -> Option<[&mut T; N]> {
+		let keys = positions.map(OrderedPoint::new);
+
+		let mut order: [usize; N] = std::array::from_fn(|i| i);
+
+		order.sort_unstable_by(|&a, &b| keys[a].cmp(&keys[b]));
+
+		if order.windows(2).any(|pair| keys[pair[0]] == keys[pair[1]]) {
+			return None;
+		}
+
+		let (Some(&first), Some(&last)) = (order.first(), order.last()) else {
+			return Some(std::array::from_fn(|_| unreachable!()));
+		};
+
+		let mut values: [Option<&mut T>; N] = std::array::from_fn(|_| None);
+		let mut range = self.inner.range_mut(&keys[first]..=&keys[last]);
+
+		for i in order {
+			let (key, value) = range.find(|(key, _)| **key >= keys[i])?;
+
+			if *key != keys[i] {
+				return None;
+			}
+
+			values[i] = Some(value);
+		}
+
+		Some(values.map(|value| value.unwrap_or_else(crate::lazy_unreachable!())))
+	}
+	/// Every position and value in `aabb`
+	pub fn range(&self, aabb: Aabb<i32, D>) -> impl Iterator<Item = (Point<i32, D>, &T)> {
+		Self::key_ranges(aabb, self.len())
+			.flat_map(|range| self.inner.range(range))
+			.map(|(a, b)| (a.coordinates, b))
+			.filter(move |&(position, _)| aabb.contains(position))
+	}
+	/// Every position and mutable value in `aabb`
+	///
+	/// Unlike [`Self::range`] this walks every value from the first to the last position of `aabb` in the order of
+	/// [`OrderedPoint`], including the ones outside of `aabb`
+	pub fn range_mut(
+		&mut self,
+		aabb: Aabb<i32, D>,
+	) -> impl Iterator<Item = (Point<i32, D>, &mut T)> {
+		let range = (!aabb.is_empty())
+			.then(|| OrderedPoint::new(aabb.min)..=OrderedPoint::new(aabb.max.map(|max| max - 1)));
+
+		range
+			.map(|range| self.inner.range_mut(range))
+			.into_iter()
+			.flatten()
+			.map(|(a, b)| (a.coordinates, b))
+			.filter(move |(position, _)| aabb.contains(*position))
+	}
+	/// Ranges of keys covering `aabb`, one per row along the first axis
+	///
+	/// Rows along the first axis are contiguous in the order of [`OrderedPoint`]. If there are more rows
+	/// than `len` values a single range from the first to the last position of `aabb` is cheaper
+	fn key_ranges(
+		aabb: Aabb<i32, D>,
+		len: usize,
+	) -> impl Iterator<Item = std::ops::RangeInclusive<OrderedPoint<D>>> {
+		let last = aabb.max.map(|max| max - 1);
+
+		let rows = (!aabb.is_empty() && D > 0).then(|| {
+			let mut rows = aabb;
+
+			rows.max[0] = rows.min[0] + 1;
+
+			rows
+		});
+
+		let count = rows.map_or(0, |rows| {
+			rows.extents()
+				.iter()
+				.try_fold(1usize, |count, &extent| count.checked_mul(extent))
+				.unwrap_or(usize::MAX)
+		});
+
+		let slab = (count > len || D == 0) && !aabb.is_empty();
+
+		let slab = slab.then(|| OrderedPoint::new(aabb.min)..=OrderedPoint::new(last));
+		let rows = rows.filter(|_| slab.is_none()).map(|rows| {
+			rows.points().map(move |first| {
+				let mut row_last = first;
+
+				row_last[0] = last[0];
+
+				OrderedPoint::new(first)..=OrderedPoint::new(row_last)
+			})
+		});
+
+		slab.into_iter().chain(rows.into_iter().flatten())
+	}
 	pub fn values(&self) -> impl Iterator<Item = &T> {
 		self.inner.values()
 	}
@@ -72,6 +179,17 @@ impl<T, const D: usize> PositionMap<T, D> {
 	}
 }
 
+impl<T, const D: usize> FromIterator<(Point<i32, D>, T)> for PositionMap<T, D> {
+	fn from_iter<I: IntoIterator<Item = (Point<i32, D>, T)>>(iter: I) -> Self {
+		Self {
+			inner: iter
+				.into_iter()
+				.map(|(position, value)| (OrderedPoint::new(position), value))
+				.collect(),
+		}
+	}
+}
+
 impl<T, const D: usize> Default for PositionMap<T, D> {
 	fn default() -> Self {
 		Self::new()
@@ -114,3 +232,77 @@ const _: () = {
 		}
 	}
 };
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// `Chunk`s in rows `y = -1, 0, 1` spread from `x = -1000` to `x = 1000`
+	fn slab() -> PositionMap<i32, 2> {
+		(-1000..=1000)
+			.flat_map(|x| (-1..=1).map(move |y| (Point::from([x, y]), x * 10 + y)))
+			.collect()
+	}
+
+	#[test]
+	fn test_range() {
+		let mut map = slab();
+
+		let aabb = Aabb::new(Point::from([-1, -1]), Point::from([2, 2]));
+
+		let found: Vec<_> = map.range(aabb).map(|(position, _)| position).collect();
+
+		assert_eq!(found, aabb.points().collect::<Vec<_>>());
+
+		// only the rows of `aabb` are visited, not the whole slab between its corners
+		let visited: usize = PositionMap::<i32, 2>::key_ranges(aabb, map.len())
+			.map(|range| map.inner.range(range).count())
+			.sum();
+
+		assert_eq!(visited, 9);
+
+		for (position, value) in map.range_mut(aabb) {
+			*value = position.x + position.y;
+		}
+
+		assert_eq!(map.get(Point::from([1, 1])), Some(&2));
+		assert_eq!(map.get(Point::from([2, 1])), Some(&21));
+		assert_eq!(
+			map.range(Aabb::new(Point::from([0, 5]), Point::from([1, 9])))
+				.count(),
+			0
+		);
+	}
+
+	#[test]
+	fn test_get_many_mut() {
+		let mut map = slab();
+
+		let [a, b] = map
+			.get_many_mut([Point::from([-1000, 1]), Point::from([1000, -1])])
+			.unwrap();
+
+		std::mem::swap(a, b);
+
+		assert_eq!(map.get(Point::from([-1000, 1])), Some(&9999));
+		assert_eq!(map.get(Point::from([1000, -1])), Some(&-9999));
+		assert!(map
+			.get_many_mut([Point::from([0, 0]), Point::from([0, 0])])
+			.is_none());
+		assert!(map
+			.get_many_mut([Point::from([0, 0]), Point::from([0, 2])])
+			.is_none());
+		assert!(map.get_many_mut([]).is_some());
+
+		// returned in the order of `positions`, not of the map
+		let [a, b, c] = map
+			.get_many_mut([
+				Point::from([5, 1]),
+				Point::from([-5, -1]),
+				Point::from([0, 0]),
+			])
+			.unwrap();
+
+		assert_eq!((*a, *b, *c), (51, -51, 0));
+	}
+}
@@ -8,7 +8,7 @@ use std::cmp::Ordering;
 	feature = "serde-serialize",
 	derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "serde_serialize", serde(transparent))]
+#[cfg_attr(feature = "serde-serialize", serde(transparent))]
 pub struct OrderedPoint<const D: usize> {
 	pub coordinates: math::Point<i32, D>,
 }
@@ -29,13 +29,7 @@ impl<const D: usize> Eq for OrderedPoint<D> {}
 
 impl<const D: usize> PartialOrd for OrderedPoint<D> {
 	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-		self.coordinates
-			.iter()
-			.rev()
-			.zip(other.coordinates.iter().rev())
-			.try_fold(Ordering::Equal, |acc, (a, b)| {
-				Some(acc.then(a.partial_cmp(b)?))
-			})
+		Some(self.cmp(other))
 	}
 }
 
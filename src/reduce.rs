@@ -0,0 +1,102 @@
+//! Reductions of the blocks covered by a block of a downsampled `Chunk`, see [`Chunk::downsample`](crate::Chunk::downsample)
+//!
+//! # Panics
+//! Every reduction panics if `blocks` is empty
+
+use num::NumCast;
+use num::ToPrimitive;
+
+/// The most common block, ties go to the block which comes first
+pub fn majority<T: Clone + PartialEq>(blocks: &[&T]) -> T {
+	let count = |block: &T| blocks.iter().filter(|&&other| other == block).count();
+
+	let mut best = blocks[0];
+	let mut best_count = count(best);
+
+	for &block in &blocks[1..] {
+		let block_count = count(block);
+
+		if block_count > best_count {
+			best = block;
+			best_count = block_count;
+		}
+	}
+
+	best.clone()
+}
+
+/// The greatest block, the first one if several are equal
+pub fn max<T: Clone + PartialOrd>(blocks: &[&T]) -> T {
+	blocks[1..]
+		.iter()
+		.fold(
+			blocks[0],
+			|max, &block| if block > max { block } else { max },
+		)
+		.clone()
+}
+
+/// The least block, the first one if several are equal
+pub fn min<T: Clone + PartialOrd>(blocks: &[&T]) -> T {
+	blocks[1..]
+		.iter()
+		.fold(
+			blocks[0],
+			|min, &block| if block < min { block } else { min },
+		)
+		.clone()
+}
+
+/// The mean of the blocks, computed in `f64` and converted back with [`NumCast`] which truncates integers
+pub fn average<T: Copy + ToPrimitive + NumCast>(blocks: &[&T]) -> T {
+	assert!(!blocks.is_empty(), "no blocks to average");
+
+	let sum: f64 = blocks
+		.iter()
+		.map(|block| block.to_f64().expect("block out of range"))
+		.sum();
+
+	T::from(sum / blocks.len() as f64).expect("average out of range")
+}
+
+/// [`majority`] of the blocks for which `solid` returns `true`, of all blocks if none is solid
+///
+/// Keeps thin solid structures such as walls in coarse levels
+pub fn any<T: Clone + PartialEq>(mut solid: impl FnMut(&T) -> bool) -> impl FnMut(&[&T]) -> T {
+	move |blocks| {
+		let solids: Vec<_> = blocks
+			.iter()
+			.copied()
+			.filter(|&block| solid(block))
+			.collect();
+
+		if solids.is_empty() {
+			majority(blocks)
+		} else {
+			majority(&solids)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_reductions() {
+		let blocks = [3, 1, 4, 1, 5, 9, 2, 6];
+		let blocks: Vec<_> = blocks.iter().collect();
+
+		assert_eq!(majority(&blocks), 1);
+		assert_eq!(majority(&blocks[..3]), 3);
+		assert_eq!(max(&blocks), 9);
+		assert_eq!(min(&blocks), 1);
+		assert_eq!(average(&blocks), 3);
+		assert_eq!(average(&[&1.0, &2.0]), 1.5);
+
+		let mut any = any(|&block: &i32| block >= 5);
+
+		assert_eq!(any(&blocks), 5);
+		assert_eq!(any(&blocks[..4]), 1);
+	}
+}
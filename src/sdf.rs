@@ -0,0 +1,242 @@
+//! Signed distance fields and constructive solid geometry on them
+//!
+//! Distances are negative inside of a shape and positive outside of it, as expected by
+//! [`mesh::marching_cubes`](crate::mesh::marching_cubes) with an `iso` of `0`
+
+use crate::math::Vector;
+
+use num::Float;
+
+/// Squared distance of every element of `f` to the nearest `q` minimizing `(p - q)^2 + f[q]`, `f` is overwritten
+///
+/// Felzenszwalb and Huttenlocher's lower envelope of parabolas, infinite elements are never the nearest
+fn transform_line<F: Float>(f: &mut [F], v: &mut Vec<usize>, z: &mut Vec<F>) {
+	let as_float = |i: usize| F::from(i).unwrap_or_else(crate::lazy_unreachable!());
+
+	v.clear();
+	z.clear();
+
+	for q in (0..f.len()).filter(|&q| f[q] != F::infinity()) {
+		let height = f[q] + as_float(q * q);
+
+		loop {
+			let Some(&p) = v.last() else {
+				v.push(q);
+				z.push(F::neg_infinity());
+
+				break;
+			};
+
+			let s = (height - (f[p] + as_float(p * p))) / (as_float(q) - as_float(p)) / as_float(2);
+
+			if s <= z[z.len() - 1] {
+				v.pop();
+				z.pop();
+
+				continue;
+			}
+
+			v.push(q);
+			z.push(s);
+
+			break;
+		}
+	}
+
+	if v.is_empty() {
+		return;
+	}
+
+	let heights: Vec<_> = v.iter().map(|&p| f[p]).collect();
+	let mut k = 0;
+
+	for (q, distance) in f.iter_mut().enumerate() {
+		while k + 1 < v.len() && z[k + 1] < as_float(q) {
+			k += 1;
+		}
+
+		let offset = as_float(q.abs_diff(v[k]));
+
+		*distance = offset * offset + heights[k];
+	}
+}
+
+/// Exact squared euclidean distance transform of a grid in column major order, `0` at features and infinite elsewhere
+fn transform<F: Float, const B: usize>(grid: &mut [F], extents: Vector<usize, B>) {
+	let mut line = Vec::new();
+	let mut v = Vec::new();
+	let mut z = Vec::new();
+
+	let mut stride = 1;
+
+	for axis in 0..B {
+		let length = extents[axis];
+		let step = stride * length;
+
+		for start in (0..grid.len()).filter(|&index| index % step < stride) {
+			line.clear();
+			line.extend((0..length).map(|i| grid[start + i * stride]));
+
+			transform_line(&mut line, &mut v, &mut z);
+
+			for (i, &distance) in line.iter().enumerate() {
+				grid[start + i * stride] = distance;
+			}
+		}
+
+		stride = step;
+	}
+}
+
+/// Signed euclidean distance field of a grid of `extents` in column major order whose blocks are `inside`
+///
+/// Distances are measured between block centers and shifted by half a block, so that the surface lies
+/// between inside and outside blocks. Without any inside or outside block distances are infinite
+pub(crate) fn signed_distance<F: Float, const B: usize>(
+	extents: Vector<usize, B>,
+	inside: &[bool],
+) -> Vec<F> {
+	let half = F::from(0.5).unwrap_or_else(crate::lazy_unreachable!());
+
+	let feature = |feature: bool| {
+		let mut grid: Vec<_> = inside
+			.iter()
+			.map(|&inside| {
+				if inside == feature {
+					F::zero()
+				} else {
+					F::infinity()
+				}
+			})
+			.collect();
+
+		transform(&mut grid, extents);
+
+		grid
+	};
+
+	let to_inside = feature(true);
+	let to_outside = feature(false);
+
+	inside
+		.iter()
+		.zip(to_inside.into_iter().zip(to_outside))
+		.map(|(&inside, (to_inside, to_outside))| {
+			if inside {
+				half - to_outside.sqrt()
+			} else {
+				to_inside.sqrt() - half
+			}
+		})
+		.collect()
+}
+
+/// Union of two shapes
+pub fn union<F: Float>(a: F, b: F) -> F {
+	a.min(b)
+}
+
+/// Intersection of two shapes
+pub fn intersection<F: Float>(a: F, b: F) -> F {
+	a.max(b)
+}
+
+/// Shape `a` without shape `b`
+pub fn subtraction<F: Float>(a: F, b: F) -> F {
+	a.max(-b)
+}
+
+/// Polynomial smooth minimum, [`union`] with the edges where the shapes meet rounded over about `k` blocks
+pub fn smooth_union<F: Float>(a: F, b: F, k: F) -> F {
+	if k <= F::zero() {
+		return union(a, b);
+	}
+
+	let h = (k - (a - b).abs()).max(F::zero()) / k;
+	let quarter = F::from(0.25).unwrap_or_else(crate::lazy_unreachable!());
+
+	a.min(b) - h * h * k * quarter
+}
+
+/// [`intersection`] rounded over about `k` blocks
+pub fn smooth_intersection<F: Float>(a: F, b: F, k: F) -> F {
+	-smooth_union(-a, -b, k)
+}
+
+/// [`subtraction`] rounded over about `k` blocks
+pub fn smooth_subtraction<F: Float>(a: F, b: F, k: F) -> F {
+	smooth_intersection(a, -b, k)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::math::Aabb;
+
+	/// Signed distance computed by comparing every pair of blocks
+	fn brute_force<const B: usize>(extents: Vector<usize, B>, inside: &[bool]) -> Vec<f64> {
+		let points: Vec<_> = Aabb::<i32, B>::from_extents(extents).points().collect();
+
+		points
+			.iter()
+			.zip(inside)
+			.map(|(a, &a_inside)| {
+				let distance = points
+					.iter()
+					.zip(inside)
+					.filter(|&(_, &b_inside)| b_inside != a_inside)
+					.map(|(b, _)| (a - b).map(f64::from).norm())
+					.fold(f64::INFINITY, f64::min);
+
+				if a_inside {
+					0.5 - distance
+				} else {
+					distance - 0.5
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn test_signed_distance() {
+		let extents = Vector::from([5, 5]);
+		let mut inside = vec![false; 25];
+
+		inside[12] = true;
+
+		let sdf = signed_distance::<f64, 2>(extents, &inside);
+
+		assert_eq!(sdf[12], -0.5);
+		assert_eq!(sdf[13], 0.5);
+		assert_eq!(sdf[14], 1.5);
+		assert_eq!(sdf[18], 2f64.sqrt() - 0.5);
+		assert_eq!(sdf, brute_force(extents, &inside));
+
+		let extents = Vector::from([6, 5, 4]);
+		let inside: Vec<_> = (0..120).map(|i: usize| (i * 37 % 11) < 3).collect();
+
+		let sdf = signed_distance::<f64, 3>(extents, &inside);
+
+		for (a, b) in sdf.iter().zip(brute_force(extents, &inside)) {
+			assert!((a - b).abs() < 1e-9);
+		}
+
+		let empty = signed_distance::<f32, 1>(Vector::from([3]), &[false; 3]);
+
+		assert_eq!(empty, [f32::INFINITY; 3]);
+	}
+
+	#[test]
+	fn test_csg() {
+		assert_eq!(union(1.0, -2.0), -2.0);
+		assert_eq!(intersection(1.0, -2.0), 1.0);
+		assert_eq!(subtraction(-1.0, -2.0), 2.0);
+		assert_eq!(subtraction(-1.0, 2.0), -1.0);
+
+		// far from where the shapes meet nothing is rounded
+		assert_eq!(smooth_union(1.0, 5.0, 2.0), 1.0);
+		assert_eq!(smooth_union(1.0, 1.0, 2.0), 0.5);
+		assert!(smooth_intersection(1.0, 1.5, 2.0) > 1.5);
+		assert_eq!(smooth_subtraction(-3.0, -3.0, 0.0), 3.0);
+	}
+}
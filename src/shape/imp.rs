@@ -19,9 +19,14 @@ pub mod rt {
 	}
 
 	impl<const B: usize> Shape<B> for Uniform<B> {
+		type Layout = layout::ColumnMajor;
+
 		fn extents(&self) -> math::Vector<usize, B> {
 			math::Vector::from_element(self.stride())
 		}
+		fn layout(&self) -> Self::Layout {
+			layout::ColumnMajor
+		}
 		fn capacity(&self) -> usize {
 			self.stride()
 				.pow(B.try_into().expect("more than `u32::MAX` dimensions"))
@@ -55,9 +60,14 @@ pub mod rt {
 	}
 
 	impl<const B: usize> Shape<B> for Multiform<B> {
+		type Layout = layout::ColumnMajor;
+
 		fn extents(&self) -> math::Vector<usize, B> {
 			self.extents
 		}
+		fn layout(&self) -> Self::Layout {
+			layout::ColumnMajor
+		}
 	}
 
 	#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
@@ -77,12 +87,17 @@ pub mod rt {
 	}
 
 	impl<const B: usize> Shape<B> for Dynamic<B> {
+		type Layout = layout::ColumnMajor;
+
 		fn extents(&self) -> math::Vector<usize, B> {
 			match self {
 				Self::Uniform(uniform) => uniform.extents(),
 				Self::Multiform(multiform) => multiform.extents(),
 			}
 		}
+		fn layout(&self) -> Self::Layout {
+			layout::ColumnMajor
+		}
 		fn capacity(&self) -> usize {
 			match self {
 				Self::Uniform(uniform) => uniform.capacity(),
@@ -90,6 +105,85 @@ pub mod rt {
 			}
 		}
 	}
+
+	/// A hyperrectangle covering `[min, max)` instead of `[0, extents)`
+	///
+	/// Blocks are stored in [`ColumnMajor`](layout::ColumnMajor) order starting from `min`,
+	/// [`with_layout`](Shape::with_layout) orders them relative to `min` as well
+	#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+	#[cfg_attr(
+		feature = "serde-serialize",
+		derive(serde::Serialize, serde::Deserialize)
+	)]
+	pub struct Bounded<const B: usize> {
+		min: math::Point<i32, B>,
+		max: math::Point<i32, B>,
+	}
+	impl<const B: usize> Bounded<B> {
+		/// # Panics
+		/// This function panics if `min` is greater than `max` on any axis
+		pub fn new(min: math::Point<i32, B>, max: math::Point<i32, B>) -> Self {
+			assert!(
+				min.iter().zip(max.iter()).all(|(min, max)| min <= max),
+				"`min` is greater than `max`"
+			);
+
+			Self { min, max }
+		}
+		/// A [`Bounded`] with `radius` blocks on each side of the origin, `[-radius, radius]`
+		pub fn centered(radius: math::Vector<i32, B>) -> Self {
+			Self::new((-radius).into(), radius.add_scalar(1).into())
+		}
+		pub fn min(&self) -> math::Point<i32, B> {
+			self.min
+		}
+		pub fn max(&self) -> math::Point<i32, B> {
+			self.max
+		}
+	}
+
+	impl<const B: usize> Default for Bounded<B> {
+		fn default() -> Self {
+			Self::new(math::Point::origin(), math::Point::origin())
+		}
+	}
+
+	impl<const B: usize> Shape<B> for Bounded<B> {
+		type Layout = layout::ColumnMajor;
+
+		fn extents(&self) -> math::Vector<usize, B> {
+			math::matrix_cast(self.max - self.min).unwrap_or_else(crate::lazy_unreachable!())
+		}
+		fn layout(&self) -> Self::Layout {
+			layout::ColumnMajor
+		}
+		fn bounds(&self) -> math::Aabb<i32, B> {
+			math::Aabb::new(self.min, self.max)
+		}
+		fn position_to_index(&self, block: math::Point<i32, B>) -> Option<usize> {
+			math::position_to_index_offset(self.extents(), self.min.coords, block)
+		}
+		fn index_to_position(&self, index: usize) -> Option<math::Point<i32, B>> {
+			math::index_to_position_offset(self.extents(), self.min.coords, index)
+		}
+		fn world_to_chunk_block<S: Coordinate, const W: usize, const C: usize>(
+			&self,
+			world: math::Point<S, W>,
+		) -> WorldCoordinate<S, C, B>
+		where
+			Const<B>: DimMax<Const<W>, Output = Const<W>>,
+			Const<C>: DimMax<Const<W>, Output = Const<W>>,
+		{
+			let min: math::Vector<S, B> =
+				math::matrix_cast(self.min.coords).expect("`min` out of range");
+
+			let (chunk, block) = Multiform::new(self.extents()).world_to_chunk_block(
+				world - min.resize_generic(Const::<W>, Const::<1>, S::zero()),
+			);
+
+			(chunk, block + min)
+		}
+	}
 }
 
 /// Compile time [`Shape`]s
@@ -115,9 +209,14 @@ pub mod ct {
 				}
 
 				impl<$(const $N: ::std::primitive::usize),*> $crate::Shape<$D> for $Shape<$($N),*> {
+					type Layout = $crate::shape::layout::ColumnMajor;
+
 					fn extents(&self) -> $crate::math::Vector<usize, $D> {
 						$crate::math::Vector::from([$($N),*])
 					}
+					fn layout(&self) -> Self::Layout {
+						$crate::shape::layout::ColumnMajor
+					}
 				}
 			}
 		}
@@ -142,9 +241,14 @@ pub mod ct {
 	}
 
 	impl<const S: usize, const B: usize> Shape<B> for Uniform<S, B> {
+		type Layout = layout::ColumnMajor;
+
 		fn extents(&self) -> math::Vector<usize, B> {
 			math::Vector::from_element(self.stride())
 		}
+		fn layout(&self) -> Self::Layout {
+			layout::ColumnMajor
+		}
 		fn capacity(&self) -> usize {
 			self.stride()
 				.pow(B.try_into().expect("more than `u32::MAX` dimensions"))
@@ -157,3 +261,87 @@ pub mod ct {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_bounded() {
+		let shape = rt::Bounded::centered(math::Vector::from([1, 2]));
+
+		assert_eq!(shape.extents(), math::Vector::from([3, 5]));
+		assert_eq!(
+			shape.position_to_index(math::Point::from([-1, -2])),
+			Some(0)
+		);
+		assert_eq!(shape.position_to_index(math::Point::from([1, 2])), Some(14));
+		assert_eq!(shape.position_to_index(math::Point::from([2, 0])), None);
+		assert_eq!(shape.index_to_position(7), Some(math::Point::from([0, 0])));
+
+		for index in 0..shape.capacity() {
+			let position = shape.index_to_position(index).unwrap();
+
+			assert_eq!(shape.position_to_index(position), Some(index));
+		}
+	}
+
+	#[test]
+	fn test_bounded_world_to_chunk_block() {
+		let shape = rt::Bounded::centered(math::Vector::from([1, 1]));
+
+		for x in -7..7 {
+			for y in -7..7 {
+				let world = math::Point::from([x, y]);
+
+				let (chunk, block): UniformWorldCoordinate<i32, 2> =
+					shape.world_to_chunk_block(world);
+
+				assert!(shape.position_to_index(block).is_some());
+				assert_eq!(shape.chunk_block_to_world::<i32, 2, 2>(chunk, block), world);
+			}
+		}
+
+		assert_eq!(
+			shape.world_to_chunk_block::<i32, 2, 2>(math::Point::from([0, 0])),
+			(math::Point::from([0, 0]), math::Point::from([0, 0]))
+		);
+		assert_eq!(
+			shape.world_to_chunk_block::<i32, 2, 2>(math::Point::from([2, -2])),
+			(math::Point::from([1, -1]), math::Point::from([-1, 1]))
+		);
+	}
+
+	#[test]
+	fn test_bounded_with_layout() {
+		let bounded = rt::Bounded::centered(math::Vector::from([1, 2]));
+		let shape = bounded.with_layout(layout::RowMajor);
+
+		assert_eq!(shape.bounds(), bounded.bounds());
+		assert_eq!(
+			shape.position_to_index(math::Point::from([-1, -2])),
+			Some(0)
+		);
+		assert_eq!(
+			shape.position_to_index(math::Point::from([-1, -1])),
+			Some(1)
+		);
+		assert_eq!(shape.position_to_index(math::Point::from([0, -2])), Some(5));
+		assert_eq!(shape.position_to_index(math::Point::from([2, 0])), None);
+
+		for index in 0..shape.capacity() {
+			let position = shape.index_to_position(index).unwrap();
+
+			assert!(bounded.position_to_index(position).is_some());
+			assert_eq!(shape.position_to_index(position), Some(index));
+		}
+
+		for world in math::Aabb::new(math::Point::from([-7; 2]), math::Point::from([7; 2])).points()
+		{
+			assert_eq!(
+				shape.world_to_chunk_block::<i32, 2, 2>(world),
+				bounded.world_to_chunk_block(world)
+			);
+		}
+	}
+}
@@ -0,0 +1,515 @@
+//! [`Layout`]s decide in which order the blocks of a [`Shape`](super::Shape) are stored
+
+use crate::math;
+use crate::math::Point;
+use crate::math::Vector;
+
+/// Maps positions inside of some extents to indices in a contiguous buffer and back
+///
+/// Implementors must be bijective over `[0, extents)` and `[0, capacity)` for all `extents` they support
+pub trait Layout<const B: usize>: Copy + Eq {
+	/// Whether this [`Layout`] can order blocks within `extents`, see [`Shape::with_layout`](super::Shape::with_layout)
+	#[inline(always)]
+	fn supports(&self, extents: Vector<usize, B>) -> bool {
+		let _ = extents;
+
+		true
+	}
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize>;
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>>;
+}
+
+impl<T: Layout<B>, const B: usize> Layout<B> for &T {
+	#[inline(always)]
+	fn supports(&self, extents: Vector<usize, B>) -> bool {
+		T::supports(&**self, extents)
+	}
+	#[inline(always)]
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize> {
+		T::position_to_index(&**self, extents, position)
+	}
+	#[inline(always)]
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>> {
+		T::index_to_position(&**self, extents, index)
+	}
+}
+
+/// The first axis varies fastest, `x + y * X + z * X * Y ...`
+///
+/// This is the default [`Layout`] of every [`Shape`](super::Shape)
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ColumnMajor;
+
+impl<const B: usize> Layout<B> for ColumnMajor {
+	#[inline(always)]
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize> {
+		math::position_to_index(extents, position)
+	}
+	#[inline(always)]
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>> {
+		math::index_to_position(extents, index)
+	}
+}
+
+/// The last axis varies fastest, `z + y * Z + x * Z * Y ...`
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct RowMajor;
+
+impl<const B: usize> Layout<B> for RowMajor {
+	#[inline(always)]
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize> {
+		math::position_to_index(reversed(extents), reversed(position.coords).into())
+	}
+	#[inline(always)]
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>> {
+		math::index_to_position(reversed(extents), index)
+			.map(|p: Point<i32, B>| reversed(p.coords).into())
+	}
+}
+
+#[inline(always)]
+fn reversed<T: math::Coordinate, const B: usize>(v: Vector<T, B>) -> Vector<T, B> {
+	Vector::from_fn(|i, _| v[B - 1 - i])
+}
+
+/// Arbitrary axis order, `order[0]` varies fastest and `order[B - 1]` slowest
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct AxisOrder<const B: usize> {
+	order: Vector<usize, B>,
+}
+
+impl<const B: usize> AxisOrder<B> {
+	/// # Panics
+	/// This function panics if `order` is not a permutation of `0..B`
+	pub fn new(order: [usize; B]) -> Self {
+		let mut seen = [false; B];
+
+		for &axis in &order {
+			assert!(axis < B && !seen[axis], "`order` is not a permutation");
+
+			seen[axis] = true;
+		}
+
+		Self {
+			order: Vector::from(order),
+		}
+	}
+	pub fn order(&self) -> Vector<usize, B> {
+		self.order
+	}
+	fn permute<T: math::Coordinate>(&self, v: Vector<T, B>) -> Vector<T, B> {
+		Vector::from_fn(|i, _| v[self.order[i]])
+	}
+	fn unpermute<T: math::Coordinate>(&self, v: Vector<T, B>) -> Vector<T, B> {
+		let mut out = v;
+
+		for (i, &axis) in self.order.iter().enumerate() {
+			out[axis] = v[i];
+		}
+
+		out
+	}
+}
+
+impl<const B: usize> Default for AxisOrder<B> {
+	fn default() -> Self {
+		Self {
+			order: Vector::from_fn(|i, _| i),
+		}
+	}
+}
+
+impl<const B: usize> Layout<B> for AxisOrder<B> {
+	#[inline(always)]
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize> {
+		math::position_to_index(self.permute(extents), self.permute(position.coords).into())
+	}
+	#[inline(always)]
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>> {
+		math::index_to_position(self.permute(extents), index)
+			.map(|p: Point<i32, B>| self.unpermute(p.coords).into())
+	}
+}
+
+/// Blocks are grouped into bricks of `brick` extents which are stored contiguously
+///
+/// Both the bricks and the blocks inside of each brick are ordered by `L`, [`RowMajor`] by default.
+/// Every extent must be a multiple of the matching `brick` extent
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Bricked<const B: usize, L = RowMajor> {
+	brick: Vector<usize, B>,
+	order: L,
+}
+
+impl<const B: usize> Bricked<B> {
+	/// Bricks of `brick` extents in [`RowMajor`] order
+	///
+	/// # Panics
+	/// This function panics if any of the `brick` extents is `0`
+	pub fn new(brick: Vector<usize, B>) -> Self {
+		Self::with_order(brick, RowMajor)
+	}
+}
+
+impl<const B: usize, L: Layout<B>> Bricked<B, L> {
+	/// Bricks of `brick` extents in `order`
+	///
+	/// # Panics
+	/// This function panics if any of the `brick` extents is `0`
+	pub fn with_order(brick: Vector<usize, B>, order: L) -> Self {
+		assert!(brick.iter().all(|&extent| extent != 0), "empty brick");
+
+		Self { brick, order }
+	}
+	pub fn brick(&self) -> Vector<usize, B> {
+		self.brick
+	}
+	pub fn order(&self) -> L {
+		self.order
+	}
+	/// Extents of the grid of bricks covering `extents`
+	pub fn grid(&self, extents: Vector<usize, B>) -> Option<Vector<usize, B>> {
+		extents
+			.iter()
+			.zip(self.brick.iter())
+			.all(|(extent, brick)| extent % brick == 0)
+			.then(|| extents.component_div(&self.brick))
+	}
+}
+
+impl<const B: usize, L: Layout<B>> Layout<B> for Bricked<B, L> {
+	fn supports(&self, extents: Vector<usize, B>) -> bool {
+		self.grid(extents)
+			.is_some_and(|grid| self.order.supports(grid) && self.order.supports(self.brick))
+	}
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize> {
+		let grid = self.grid(extents)?;
+
+		if position.iter().any(|&coordinate| coordinate < 0) {
+			return None;
+		}
+
+		let brick = math::matrix_cast::<usize, i32, B, 1>(self.brick)?;
+
+		let outer = self
+			.order
+			.position_to_index(grid, position.coords.component_div(&brick).into())?;
+		let inner = self.order.position_to_index(
+			self.brick,
+			position.coords.zip_map(&brick, std::ops::Rem::rem).into(),
+		)?;
+
+		Some(outer * self.brick.product() + inner)
+	}
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>> {
+		let grid = self.grid(extents)?;
+
+		let capacity = self.brick.product();
+
+		let outer = self.order.index_to_position(grid, index / capacity)?;
+		let inner = self.order.index_to_position(self.brick, index % capacity)?;
+
+		let brick = math::matrix_cast::<usize, i32, B, 1>(self.brick)?;
+
+		Some(Point::from(
+			outer.coords.component_mul(&brick) + inner.coords,
+		))
+	}
+}
+
+/// Z-order curve, bits of all axes are interleaved starting from the first axis
+///
+/// Every extent must be a power of two
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Morton;
+
+impl Morton {
+	fn bits<const B: usize>(extents: Vector<usize, B>) -> Option<Vector<u32, B>> {
+		extents
+			.iter()
+			.all(|extent| extent.is_power_of_two())
+			.then(|| extents.map(|extent| extent.trailing_zeros()))
+	}
+}
+
+impl<const B: usize> Layout<B> for Morton {
+	fn supports(&self, extents: Vector<usize, B>) -> bool {
+		Self::bits(extents).is_some()
+	}
+	fn position_to_index(
+		&self,
+		extents: Vector<usize, B>,
+		position: Point<i32, B>,
+	) -> Option<usize> {
+		let bits = Self::bits(extents)?;
+
+		let mut coordinates = Vector::<usize, B>::zeros();
+
+		for i in 0..B {
+			coordinates[i] = usize::try_from(position[i]).ok()?;
+
+			if coordinates[i] >= extents[i] {
+				return None;
+			}
+		}
+
+		let mut index = 0;
+		let mut out = 0;
+
+		for level in 0..bits.max() {
+			for i in 0..B {
+				if level < bits[i] {
+					index |= (coordinates[i] >> level & 1) << out;
+					out += 1;
+				}
+			}
+		}
+
+		Some(index)
+	}
+	fn index_to_position(&self, extents: Vector<usize, B>, index: usize) -> Option<Point<i32, B>> {
+		let bits = Self::bits(extents)?;
+
+		if index >= extents.product() {
+			return None;
+		}
+
+		let mut coordinates = Vector::<usize, B>::zeros();
+		let mut out = 0;
+
+		for level in 0..bits.max() {
+			for i in 0..B {
+				if level < bits[i] {
+					coordinates[i] |= (index >> out & 1) << level;
+					out += 1;
+				}
+			}
+		}
+
+		math::matrix_cast(coordinates).map(Into::into)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn round_trip<L: Layout<3>>(layout: L, extents: Vector<usize, 3>) {
+		let capacity = extents.product();
+
+		for index in 0..capacity {
+			let position = layout.index_to_position(extents, index).unwrap();
+
+			assert_eq!(layout.position_to_index(extents, position), Some(index));
+		}
+
+		assert_eq!(layout.index_to_position(extents, capacity), None);
+		assert_eq!(
+			layout.position_to_index(extents, Point::from([-1, 0, 0])),
+			None
+		);
+	}
+
+	#[test]
+	fn test_round_trip() {
+		let extents = Vector::from([3, 5, 2]);
+
+		round_trip(ColumnMajor, extents);
+		round_trip(RowMajor, extents);
+		round_trip(AxisOrder::new([1, 2, 0]), extents);
+		round_trip(Morton, Vector::from([4, 2, 8]));
+	}
+
+	#[test]
+	fn test_row_major() {
+		let extents = Vector::from([3, 5, 2]);
+
+		assert_eq!(
+			RowMajor.position_to_index(extents, Point::from([0, 0, 1])),
+			Some(1)
+		);
+		assert_eq!(
+			RowMajor.position_to_index(extents, Point::from([0, 1, 0])),
+			Some(2)
+		);
+		assert_eq!(
+			RowMajor.position_to_index(extents, Point::from([1, 0, 0])),
+			Some(10)
+		);
+	}
+
+	#[test]
+	fn test_chunk_with_layout() {
+		use crate::Chunk;
+		use crate::Shape;
+
+		let shape = crate::ct::Multiform3::<2, 3, 4>::new().with_layout(RowMajor);
+		let chunk = Chunk::<Vec<usize>, _, 3>::from_shape_index(shape, |index| index);
+
+		assert_eq!(chunk.block(Point::from([0, 0, 3])), Some(&3));
+		assert_eq!(chunk.block(Point::from([1, 2, 3])), Some(&23));
+
+		for (position, &index) in chunk.block_positions() {
+			assert_eq!(shape.position_to_index(position), Some(index));
+		}
+	}
+
+	#[test]
+	fn test_morton_supports() {
+		use crate::Shape;
+
+		assert!(Morton.supports(Vector::from([4, 2, 8])));
+		assert!(!Morton.supports(Vector::from([4, 6])));
+
+		let shape = crate::ct::Uniform::<4, 2>.with_layout(Morton);
+
+		assert_eq!(shape.index_to_position(15), Some(Point::from([3, 3])));
+	}
+
+	#[test]
+	#[should_panic(expected = "does not support the extents")]
+	fn test_morton_unsupported() {
+		use crate::Shape;
+
+		crate::ct::Uniform::<6, 2>.with_layout(Morton);
+	}
+
+	#[test]
+	fn test_bricked_supports() {
+		let bricked = Bricked::new(Vector::from([2, 4]));
+
+		assert!(bricked.supports(Vector::from([4, 8])));
+		assert!(!bricked.supports(Vector::from([4, 6])));
+		assert!(!Bricked::with_order(Vector::from([2, 2]), Morton).supports(Vector::from([6, 6])));
+	}
+
+	#[test]
+	#[should_panic(expected = "does not support the extents")]
+	fn test_bricked_unsupported() {
+		use crate::Shape;
+
+		crate::ct::Uniform::<6, 2>.with_layout(Bricked::new(Vector::from([4, 4])));
+	}
+
+	#[test]
+	fn test_axis_order() {
+		let extents = Vector::from([3, 5, 2]);
+
+		for index in 0..30 {
+			assert_eq!(
+				AxisOrder::new([2, 1, 0]).index_to_position(extents, index),
+				RowMajor.index_to_position(extents, index)
+			);
+			assert_eq!(
+				AxisOrder::default().index_to_position(extents, index),
+				ColumnMajor.index_to_position(extents, index)
+			);
+		}
+	}
+
+	#[test]
+	fn test_bricked() {
+		let extents = Vector::from([4, 6, 8]);
+		let bricked = Bricked::with_order(Vector::from([2, 3, 4]), ColumnMajor);
+
+		round_trip(bricked, extents);
+		round_trip(Bricked::new(Vector::from([2, 2, 2])), extents);
+		round_trip(
+			Bricked::with_order(Vector::from([1; 3]), ColumnMajor),
+			extents,
+		);
+
+		// blocks inside of a brick are row major by default
+		let row_major = Bricked::new(Vector::from([2, 3, 4]));
+
+		for (position, index) in [
+			([0, 0, 1], 1),
+			([0, 1, 0], 4),
+			([1, 0, 0], 12),
+			([1, 2, 3], 23),
+		] {
+			assert_eq!(
+				row_major.position_to_index(extents, Point::from(position)),
+				Some(index)
+			);
+		}
+
+		// the first brick occupies the first `2 * 3 * 4` indices
+		for index in 0..24 {
+			let position = bricked.index_to_position(extents, index).unwrap();
+
+			assert!(position.coords < Vector::from([2, 3, 4]));
+		}
+
+		assert_eq!(
+			bricked.position_to_index(extents, Point::from([2, 0, 0])),
+			Some(24)
+		);
+		assert_eq!(
+			bricked.position_to_index(Vector::from([5, 6, 8]), Point::from([0; 3])),
+			None
+		);
+	}
+
+	#[test]
+	fn test_morton() {
+		let extents = Vector::from([4; 3]);
+
+		assert_eq!(
+			Morton.position_to_index(extents, Point::from([1, 1, 1])),
+			Some(0b111)
+		);
+		assert_eq!(
+			Morton.position_to_index(extents, Point::from([2, 0, 1])),
+			Some(0b001_100)
+		);
+		assert_eq!(
+			Morton.position_to_index(Vector::from([3, 4, 4]), Point::from([0; 3])),
+			None
+		);
+	}
+}
@@ -1,10 +1,13 @@
 mod imp;
 
+pub mod layout;
+
 use math::*;
 
 // use std::ops::Deref;
 
 pub use imp::*;
+pub use layout::Layout;
 
 use crate::math;
 
@@ -12,19 +15,40 @@ pub(crate) type UniformWorldCoordinate<S, const D: usize> = WorldCoordinate<S, D
 pub(crate) type WorldCoordinate<S, const C: usize, const B: usize> = (Point<S, C>, Point<S, B>);
 
 pub trait Shape<const B: usize>: Sized + Copy + Eq {
+	type Layout: Layout<B>;
+
 	fn extents(&self) -> Vector<usize, B>;
+	fn layout(&self) -> Self::Layout;
 
 	#[inline(always)]
 	fn capacity(&self) -> usize {
 		self.extents().into_iter().product()
 	}
+	/// Positions of the blocks in this [`Shape`], `[0, extents)` unless overridden
+	#[inline(always)]
+	fn bounds(&self) -> Aabb<i32, B> {
+		Aabb::from_extents(self.extents())
+	}
 	#[inline(always)]
 	fn position_to_index(&self, block: Point<i32, B>) -> Option<usize> {
-		position_to_index(self.extents(), block)
+		self.layout().position_to_index(self.extents(), block)
 	}
 	#[inline(always)]
 	fn index_to_position(&self, index: usize) -> Option<Point<i32, B>> {
-		index_to_position(self.extents(), index)
+		self.layout().index_to_position(self.extents(), index)
+	}
+	/// Replaces the [`Layout`] of this [`Shape`] while keeping its extents
+	///
+	/// # Panics
+	/// This function panics if `layout` does not support the extents of this [`Shape`], see [`Layout::supports`]
+	#[inline(always)]
+	fn with_layout<L: Layout<B>>(self, layout: L) -> WithLayout<Self, L> {
+		assert!(
+			layout.supports(self.extents()),
+			"the layout does not support the extents of the shape"
+		);
+
+		WithLayout::new(self, layout)
 	}
 	#[inline(always)]
 	fn world_to_chunk_block<S: Coordinate, const W: usize, const C: usize>(
@@ -100,17 +124,27 @@ pub trait UniformShape<const B: usize>: Shape<B> {
 	fn stride(&self) -> usize;
 }
 
-impl<'a, T: Shape<B>, const B: usize> Shape<B> for &'a T {
+impl<T: Shape<B>, const B: usize> Shape<B> for &T {
+	type Layout = T::Layout;
+
 	#[inline(always)]
 	fn extents(&self) -> math::Vector<usize, B> {
 		T::extents(&**self)
 	}
+	#[inline(always)]
+	fn layout(&self) -> Self::Layout {
+		T::layout(&**self)
+	}
 
 	#[inline(always)]
 	fn capacity(&self) -> usize {
 		T::capacity(&**self)
 	}
 	#[inline(always)]
+	fn bounds(&self) -> Aabb<i32, B> {
+		T::bounds(&**self)
+	}
+	#[inline(always)]
 	fn position_to_index(&self, block: Point<i32, B>) -> Option<usize> {
 		T::position_to_index(&**self, block)
 	}
@@ -161,8 +195,92 @@ impl<'a, T: Shape<B>, const B: usize> Shape<B> for &'a T {
 	}
 }
 
-impl<'a, T: UniformShape<B>, const B: usize> UniformShape<B> for &'a T {
+impl<T: UniformShape<B>, const B: usize> UniformShape<B> for &T {
 	fn stride(&self) -> usize {
 		T::stride(&**self)
 	}
 }
+
+/// A [`Shape`] `S` whose blocks are stored in the order of `L` instead of `S::Layout`
+///
+/// Keeps the [`bounds`](Shape::bounds) of `S`, `L` orders the blocks relative to their minimum
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct WithLayout<S, L> {
+	pub shape: S,
+	pub layout: L,
+}
+
+impl<S, L> WithLayout<S, L> {
+	/// Unlike [`Shape::with_layout`] this does not check whether `layout` supports the extents of `shape`
+	pub const fn new(shape: S, layout: L) -> Self {
+		Self { shape, layout }
+	}
+}
+
+impl<T: Shape<B>, L: Layout<B>, const B: usize> Shape<B> for WithLayout<T, L> {
+	type Layout = L;
+
+	#[inline(always)]
+	fn extents(&self) -> math::Vector<usize, B> {
+		self.shape.extents()
+	}
+	#[inline(always)]
+	fn layout(&self) -> Self::Layout {
+		self.layout
+	}
+	#[inline(always)]
+	fn capacity(&self) -> usize {
+		self.shape.capacity()
+	}
+	#[inline(always)]
+	fn bounds(&self) -> Aabb<i32, B> {
+		self.shape.bounds()
+	}
+	#[inline(always)]
+	fn position_to_index(&self, block: Point<i32, B>) -> Option<usize> {
+		let min = self.bounds().min.coords;
+
+		self.layout.position_to_index(self.extents(), block - min)
+	}
+	#[inline(always)]
+	fn index_to_position(&self, index: usize) -> Option<Point<i32, B>> {
+		let min = self.bounds().min.coords;
+
+		self.layout
+			.index_to_position(self.extents(), index)
+			.map(|position| position + min)
+	}
+	#[inline(always)]
+	fn world_to_chunk_block<S: Coordinate, const W: usize, const C: usize>(
+		&self,
+		world: Point<S, W>,
+	) -> WorldCoordinate<S, C, B>
+	where
+		Const<B>: DimMax<Const<W>, Output = Const<W>>,
+		Const<C>: DimMax<Const<W>, Output = Const<W>>,
+	{
+		self.shape.world_to_chunk_block(world)
+	}
+	#[inline(always)]
+	fn chunk_block_to_world<S: Coordinate, const W: usize, const C: usize>(
+		&self,
+		chunk: Point<S, C>,
+		block: Point<S, B>,
+	) -> Point<S, W>
+	where
+		Const<B>: DimMax<Const<W>, Output = Const<W>>,
+		Const<C>: DimMax<Const<W>, Output = Const<W>>,
+	{
+		self.shape.chunk_block_to_world(chunk, block)
+	}
+}
+
+impl<S: UniformShape<B>, L: Layout<B>, const B: usize> UniformShape<B> for WithLayout<S, L> {
+	fn stride(&self) -> usize {
+		self.shape.stride()
+	}
+}
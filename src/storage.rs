@@ -111,7 +111,7 @@ impl<T> FromFn for Vec<T> {
 
 impl<T> FromFn for Box<[T]> {
 	fn from_fn(capacity: usize, f: impl FnMut(usize) -> Self::Item) -> Self {
-		Vec::from_fn(capacity, f).into_boxed_slice()
+		<Vec<T> as FromFn>::from_fn(capacity, f).into_boxed_slice()
 	}
 }
 
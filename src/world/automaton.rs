@@ -0,0 +1,274 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Neighborhood;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::PositionMap;
+use crate::Shape;
+
+use rayon::prelude::*;
+
+/// Runs a cellular automaton on a [`Multiform`], writing each generation into the storage of the one before it
+///
+/// The storage of the `Chunk`s of a generation is kept after the next generation replaced it, [`Self::step`] reuses it
+/// instead of allocating new storage for every `Chunk`
+#[derive(Debug, Clone)]
+pub struct Automaton<T> {
+	neighborhood: Neighborhood,
+	spare: Vec<T>,
+}
+
+impl<T> Automaton<T> {
+	pub fn new(neighborhood: Neighborhood) -> Self {
+		Self {
+			neighborhood,
+			spare: Vec::new(),
+		}
+	}
+	pub fn neighborhood(&self) -> Neighborhood {
+		self.neighborhood
+	}
+	/// Advances every block of `world` to `rule(block, neighbors)` at once, see [`Multiform::step`]
+	pub fn step<S, const W: usize, const C: usize, const B: usize>(
+		&mut self,
+		world: &mut Multiform<T, S, W, C, B>,
+		mut rule: impl FnMut(&T::Item, &[&T::Item]) -> T::Item,
+	) where
+		T: ContiguousMemoryMut + FromFn,
+		T::Item: Default + PartialEq,
+		S: Shape<B>,
+		math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+		math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	{
+		let offsets: Vec<_> = self.neighborhood.offsets().collect();
+
+		let mut next = PositionMap::new();
+
+		for chunk in world.automaton_chunks(self.neighborhood) {
+			let (storage, alive) =
+				world.automaton_chunk(chunk, &offsets, self.spare.pop(), &mut rule);
+
+			if alive {
+				next.insert(chunk, storage);
+			} else {
+				self.spare.push(storage);
+			}
+		}
+
+		self.swap(world, next);
+	}
+	/// [`Self::step`] with every `Chunk` computed in parallel
+	pub fn par_step<S, const W: usize, const C: usize, const B: usize>(
+		&mut self,
+		world: &mut Multiform<T, S, W, C, B>,
+		rule: impl Fn(&T::Item, &[&T::Item]) -> T::Item + Sync,
+	) where
+		T: ContiguousMemoryMut + FromFn + Send + Sync,
+		T::Item: Default + PartialEq + Sync,
+		S: Shape<B> + Sync,
+		math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+		math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	{
+		let offsets: Vec<_> = self.neighborhood.offsets().collect();
+
+		let chunks: Vec<_> = world
+			.automaton_chunks(self.neighborhood)
+			.into_iter()
+			.map(|chunk| (chunk, self.spare.pop()))
+			.collect();
+
+		let world_ref = &*world;
+
+		let computed: Vec<_> = chunks
+			.into_par_iter()
+			.map(|(chunk, storage)| {
+				(
+					chunk,
+					world_ref.automaton_chunk(chunk, &offsets, storage, &rule),
+				)
+			})
+			.collect();
+
+		let mut next = PositionMap::new();
+
+		for (chunk, (storage, alive)) in computed {
+			if alive {
+				next.insert(chunk, storage);
+			} else {
+				self.spare.push(storage);
+			}
+		}
+
+		self.swap(world, next);
+	}
+	/// Replaces the `Chunk`s of `world` with `next`, keeping the storage of the replaced ones
+	fn swap<S, const W: usize, const C: usize, const B: usize>(
+		&mut self,
+		world: &mut Multiform<T, S, W, C, B>,
+		next: PositionMap<T, C>,
+	) {
+		let previous = std::mem::replace(&mut world.inner, next);
+
+		self.spare
+			.extend(previous.into_iter().map(|(_, storage)| storage));
+	}
+}
+
+/// # Cellular automata
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut + FromFn,
+	T::Item: Default + PartialEq,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Every `Chunk` which may hold a block that is not default in the next generation
+	fn automaton_chunks(&self, neighborhood: Neighborhood) -> Vec<Point<i32, C>> {
+		let shape = self.shape;
+		let bounds = shape.bounds();
+		let reach = Vector::repeat(neighborhood.radius() as i32);
+
+		let chunks: PositionMap<(), C> = self
+			.positions()
+			.flat_map(|chunk| {
+				let covered = Aabb::<i32, W>::new(
+					shape.chunk_block_to_world(chunk, bounds.min),
+					shape.chunk_block_to_world(chunk, bounds.max),
+				);
+
+				self.region_chunks(covered.expand(reach))
+					.map(|(chunk, _)| (chunk, ()))
+			})
+			.collect();
+
+		chunks.iter().map(|(chunk, _)| chunk).collect()
+	}
+	/// Writes the next generation of the `Chunk` at `chunk` into `storage`, or new storage if there is none
+	///
+	/// Returns the storage and whether any of its blocks is not default
+	fn automaton_chunk(
+		&self,
+		chunk: Point<i32, C>,
+		offsets: &[Vector<i32, W>],
+		storage: Option<T>,
+		mut rule: impl FnMut(&T::Item, &[&T::Item]) -> T::Item,
+	) -> (T, bool) {
+		let shape = self.shape;
+		let default = T::Item::default();
+
+		let mut storage =
+			storage.unwrap_or_else(|| T::from_fn(shape.capacity(), |_| T::Item::default()));
+
+		let mut cursor = self.cursor();
+		let mut neighbors = Vec::with_capacity(offsets.len());
+		let mut alive = false;
+
+		for (index, slot) in storage.as_mut_slice().iter_mut().enumerate() {
+			let block = shape
+				.index_to_position(index)
+				.unwrap_or_else(crate::lazy_unreachable!());
+			let position = shape.chunk_block_to_world(chunk, block);
+
+			neighbors.clear();
+			neighbors.extend(
+				offsets
+					.iter()
+					.map(|&offset| cursor.get(position + offset).unwrap_or(&default)),
+			);
+
+			*slot = rule(cursor.get(position).unwrap_or(&default), &neighbors);
+
+			alive |= *slot != default;
+		}
+
+		(storage, alive)
+	}
+	/// Advances every block to `rule(block, neighbors)` at once, `neighbors` is in the order of [`Neighborhood::offsets`]
+	///
+	/// Blocks in missing `Chunk`s are default. `Chunk`s are created where the automaton grows
+	/// and removed when all of their blocks become default. Use an [`Automaton`] to run many generations
+	/// without allocating new storage for each of them
+	pub fn step(
+		&mut self,
+		neighborhood: Neighborhood,
+		rule: impl FnMut(&T::Item, &[&T::Item]) -> T::Item,
+	) {
+		Automaton::new(neighborhood).step(self, rule)
+	}
+	/// [`Self::step`] with every `Chunk` computed in parallel
+	pub fn par_step(
+		&mut self,
+		neighborhood: Neighborhood,
+		rule: impl Fn(&T::Item, &[&T::Item]) -> T::Item + Sync,
+	) where
+		T: Send + Sync,
+		T::Item: Sync,
+		S: Sync,
+	{
+		Automaton::new(neighborhood).par_step(self, rule)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::Uniform;
+
+	fn life(&alive: &bool, neighbors: &[&bool]) -> bool {
+		let count = neighbors.iter().filter(|&&&neighbor| neighbor).count();
+
+		matches!((alive, count), (true, 2) | (_, 3))
+	}
+
+	#[test]
+	fn test_glider() {
+		let mut world = Uniform::<Vec<bool>, ct::Uniform<4, 2>, 2>::default();
+
+		let glider = [[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]].map(Point::from);
+
+		for position in glider {
+			world.entry(world.world_to_chunk(position)).or_default();
+			*world.get_block_mut(position).unwrap() = true;
+		}
+
+		let mut parallel = world.clone();
+
+		let mut automaton = Automaton::new(Neighborhood::Moore(1));
+		let mut par_automaton = Automaton::new(Neighborhood::Moore(1));
+
+		for generation in 1..=16 {
+			automaton.step(&mut world, life);
+			par_automaton.par_step(&mut parallel, life);
+
+			assert_eq!(world, parallel);
+
+			if generation % 4 == 0 {
+				let offset = Vector::repeat(generation / 4);
+
+				for position in Aabb::new(Point::from([-2; 2]), Point::from([10; 2])).points() {
+					let alive = glider.contains(&(position - offset));
+
+					assert_eq!(world.get_block(position).copied().unwrap_or(false), alive);
+				}
+			}
+		}
+
+		// only the `Chunk`s around the glider are kept
+		assert!(world.len() <= 4);
+	}
+
+	#[test]
+	fn test_extinction() {
+		let mut world = Uniform::<Vec<bool>, ct::Uniform<2, 4>, 4>::default();
+
+		world.entry(Point::origin()).or_default().storage[0] = true;
+
+		world.step(Neighborhood::Moore(1), life);
+
+		assert!(world.is_empty());
+	}
+}
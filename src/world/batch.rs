@@ -0,0 +1,127 @@
+use super::missing::Missing;
+use super::Multiform;
+use crate::math;
+use crate::math::Point;
+use crate::position_map::OrderedPoint;
+use crate::storage::*;
+use crate::PositionMap;
+use crate::Shape;
+
+/// # Batched writes
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	T::Item: PartialEq,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Writes every `(position, block)` in `updates`, grouped by `Chunk`
+	///
+	/// Later updates to the same position win. Returns the number of blocks whose value changed in each `Chunk`,
+	/// `Chunk`s without changes are left out
+	pub fn apply_batch(
+		&mut self,
+		updates: impl IntoIterator<Item = (Point<i32, W>, T::Item)>,
+		missing: impl Missing<T, S, C, B>,
+	) -> PositionMap<usize, C> {
+		let shape = self.shape;
+
+		let mut updates: Vec<_> = updates
+			.into_iter()
+			.filter_map(|(position, block)| {
+				let (chunk, local) = shape.world_to_chunk_block(position);
+
+				Some((chunk, shape.position_to_index(local)?, block))
+			})
+			.collect();
+
+		// stable so that the order of updates to the same block is kept
+		updates.sort_by_key(|&(chunk, index, _)| (OrderedPoint::new(chunk), index));
+
+		let mut changes = PositionMap::new();
+		let mut updates = updates.into_iter().peekable();
+
+		while let Some(&(chunk, _, _)) = updates.peek() {
+			let mut group =
+				std::iter::from_fn(|| updates.next_if(|&(next, _, _)| next == chunk)).peekable();
+
+			let storage = missing.storage(&mut self.inner, shape, chunk);
+
+			let Some(storage) = storage else {
+				group.for_each(drop);
+
+				continue;
+			};
+
+			let slice = storage.as_mut_slice();
+
+			let mut changed = 0;
+
+			while let Some((_, index, block)) = group.next() {
+				// superseded by a later update
+				if group.peek().is_some_and(|&(_, next, _)| next == index) {
+					continue;
+				}
+
+				if slice[index] != block {
+					slice[index] = block;
+
+					changed += 1;
+				}
+			}
+
+			if changed > 0 {
+				changes.insert(chunk, changed);
+			}
+		}
+
+		changes
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_apply_batch() {
+		let mut world = Uniform::<Vec<u8>, ct::Uniform<4, 2>, 2>::default();
+
+		world.entry(Point::from([0, 0])).or_default();
+
+		let updates = [
+			([1, 1], 1),
+			([-1, 0], 2),
+			([2, 3], 3),
+			([1, 1], 4),
+			([0, 0], 0),
+			([5, 0], 5),
+			([-2, 0], 6),
+			([-2, 0], 0),
+		]
+		.map(|(position, block)| (Point::from(position), block));
+
+		let changes = world.apply_batch(updates, missing::Skip);
+
+		assert_eq!(
+			changes.iter().collect::<Vec<_>>(),
+			[(Point::from([0, 0]), &2)]
+		);
+		assert_eq!(world.get_block(Point::from([1, 1])), Some(&4));
+		assert_eq!(world.len(), 1);
+
+		let changes = world.apply_batch(updates, missing::Create);
+
+		assert_eq!(
+			changes.iter().collect::<Vec<_>>(),
+			[(Point::from([-1, 0]), &1), (Point::from([1, 0]), &1)]
+		);
+		assert_eq!(world.len(), 3);
+		assert_eq!(world.get_block(Point::from([-2, 0])), Some(&0));
+		assert_eq!(world.get_block(Point::from([5, 0])), Some(&5));
+	}
+}
@@ -0,0 +1,160 @@
+use super::Multiform;
+use crate::chunk::ChunkMut;
+use crate::chunk::ChunkRef;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::Shape;
+
+/// The `3^C` `Chunk`s around and including some `Chunk`, only the one in the center is mutable
+///
+/// Created by [`Multiform::chunk_neighborhood`]
+#[derive(Debug)]
+pub struct ChunkNeighborhood<'a, T, S, const C: usize, const B: usize> {
+	position: Point<i32, C>,
+	center: ChunkMut<'a, T, S, B>,
+	neighbors: Vec<Option<&'a T>>,
+}
+
+impl<'a, T, S: Shape<B>, const C: usize, const B: usize> ChunkNeighborhood<'a, T, S, C, B> {
+	fn index(offset: Vector<i32, C>) -> Option<usize> {
+		math::position_to_index(Vector::repeat(3), Point::from(offset.add_scalar(1)))
+	}
+	/// Neighborhood of `center` at `position` whose neighbors are looked up with `neighbor(position)`
+	pub(super) fn from_fn(
+		position: Point<i32, C>,
+		center: ChunkMut<'a, T, S, B>,
+		mut neighbor: impl FnMut(Point<i32, C>) -> Option<&'a T>,
+	) -> Self {
+		let mut neighbors = vec![None; 3usize.pow(C as u32)];
+
+		for offset in math::Neighborhood::Moore(1).offsets() {
+			let index = Self::index(offset).unwrap_or_else(crate::lazy_unreachable!());
+
+			neighbors[index] = neighbor(position + offset);
+		}
+
+		Self {
+			position,
+			center,
+			neighbors,
+		}
+	}
+	/// Position of the center `Chunk`
+	pub fn position(&self) -> Point<i32, C> {
+		self.position
+	}
+	pub fn center(&self) -> ChunkRef<'_, T, S, B> {
+		self.center.as_deref()
+	}
+	pub fn center_mut(&mut self) -> ChunkMut<'_, T, S, B> {
+		self.center.as_deref_mut()
+	}
+	pub fn into_center(self) -> ChunkMut<'a, T, S, B> {
+		self.center
+	}
+	/// The `Chunk` at `position + offset`, `None` if it is missing or if `offset` is not in `[-1, 1]` on every axis
+	///
+	/// The center `Chunk` is available through [`Self::center`]
+	pub fn neighbor(&self, offset: Vector<i32, C>) -> Option<ChunkRef<'a, T, S, B>> {
+		let storage = (*self.neighbors.get(Self::index(offset)?)?)?;
+
+		Some(ChunkRef::new(storage, self.center.shape))
+	}
+	/// Every existing `Chunk` around the center one
+	pub fn neighbors(&self) -> impl '_ + Iterator<Item = (Point<i32, C>, ChunkRef<'a, T, S, B>)> {
+		let shape = self.center.shape;
+
+		math::Neighborhood::Moore(1)
+			.offsets()
+			.filter_map(move |offset| {
+				let storage = self.neighbors[Self::index(offset)?]?;
+
+				Some((self.position + offset, ChunkRef::new(storage, shape)))
+			})
+	}
+}
+
+impl<T, S: Shape<B>, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// The `Chunk` at `position` mutably along with shared references to its neighbors
+	///
+	/// Returns `None` if the `Chunk` at `position` is missing
+	pub fn chunk_neighborhood(
+		&mut self,
+		position: Point<i32, C>,
+	) -> Option<ChunkNeighborhood<'_, T, S, C, B>> {
+		let shape = self.shape;
+
+		let aabb = Aabb::<i32, C>::new(position - Vector::repeat(1), position + Vector::repeat(2));
+
+		let mut center = None;
+		let mut neighbors = vec![None; 3usize.pow(C as u32)];
+
+		for (neighbor, storage) in self.inner.range_mut(aabb) {
+			let offset = neighbor - position;
+
+			if offset == Vector::<i32, C>::zeros() {
+				center = Some(storage);
+			} else {
+				let index = ChunkNeighborhood::<T, S, C, B>::index(offset)
+					.unwrap_or_else(crate::lazy_unreachable!());
+
+				neighbors[index] = Some(&*storage);
+			}
+		}
+
+		Some(ChunkNeighborhood {
+			position,
+			center: ChunkMut::new(center?, shape),
+			neighbors,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_chunk_neighborhood() {
+		let mut world = Uniform::<Vec<i32>, ct::Uniform<2, 2>, 2>::default();
+
+		for x in -2..3 {
+			for y in -2..3 {
+				world
+					.entry(Point::from([x, y]))
+					.or_insert_with(|| vec![x * 10 + y; 4]);
+			}
+		}
+
+		world.remove(Point::from([1, 1]));
+
+		let mut neighborhood = world.chunk_neighborhood(Point::from([0, 1])).unwrap();
+
+		assert_eq!(neighborhood.neighbors().count(), 7);
+		assert!(neighborhood.neighbor(Vector::from([1, 0])).is_none());
+		assert!(neighborhood.neighbor(Vector::from([0, 2])).is_none());
+
+		let sum: i32 = neighborhood
+			.neighbors()
+			.map(|(_, chunk)| chunk.storage[0])
+			.sum();
+
+		let below = neighborhood.neighbor(Vector::from([-1, -1])).unwrap();
+
+		for block in neighborhood.center_mut().iter_mut() {
+			*block = sum + below.storage[0];
+		}
+
+		// `(-10 + -9 + -8) + (0 + 2) + (10 + 12)` and `-10`
+		assert_eq!(world.get_block(Point::from([0, 2])), Some(&(-3 - 10)));
+		assert!(world.chunk_neighborhood(Point::from([1, 1])).is_none());
+	}
+}
@@ -0,0 +1,173 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Coordinate;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::Shape;
+
+use num::Float;
+
+/// Motion of a box moved by [`Multiform::sweep`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sweep<F: Coordinate, const W: usize> {
+	/// Velocity clipped such that the box does not enter any solid block
+	pub motion: Vector<F, W>,
+	/// Normal of the block face the box hit on each axis, `0` on axes on which it moved freely
+	pub normal: Vector<i32, W>,
+}
+
+impl<F: Coordinate, const W: usize> Sweep<F, W> {
+	/// Whether the box hit a block on any axis
+	pub fn collided(&self) -> bool {
+		self.normal.iter().any(|&n| n != 0)
+	}
+}
+
+/// # Collision
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Moves `aabb` by `velocity` one axis after the other, stopping on each axis at the first block for which `solid`
+	/// returns `true`
+	///
+	/// Block `p` covers `[p, p + 1)`. Blocks which `aabb` already overlaps and blocks in missing `Chunk`s never
+	/// collide, a box touching a block moves along it freely
+	pub fn sweep<F: Coordinate + Float>(
+		&self,
+		aabb: Aabb<F, W>,
+		velocity: Vector<F, W>,
+		mut solid: impl FnMut(Point<i32, W>, &T::Item) -> bool,
+	) -> Sweep<F, W> {
+		let to_i32 = |c: F| c.to_i32().expect("`aabb` out of range");
+
+		let mut cursor = self.cursor();
+		let mut aabb = aabb;
+		let mut sweep = Sweep {
+			motion: Vector::zeros(),
+			normal: Vector::zeros(),
+		};
+
+		for axis in 0..W {
+			let delta = velocity[axis];
+
+			if delta == F::zero() {
+				continue;
+			}
+
+			// blocks overlapping the box on the other axes
+			let mut cross_section = Aabb::new(
+				aabb.min.map(|c| to_i32(c.floor())),
+				aabb.max.map(|c| to_i32(c.ceil())),
+			);
+
+			let (layers, normal): (Box<dyn Iterator<Item = i32>>, _) = if delta > F::zero() {
+				let start = to_i32(aabb.max[axis].ceil());
+				let end = to_i32((aabb.max[axis] + delta).ceil());
+
+				(Box::new(start..end), -1)
+			} else {
+				let start = to_i32(aabb.min[axis].floor());
+				let end = to_i32((aabb.min[axis] + delta).floor());
+
+				(Box::new((end..start).rev()), 1)
+			};
+
+			let mut motion = delta;
+
+			for layer in layers {
+				cross_section.min[axis] = layer;
+				cross_section.max[axis] = layer + 1;
+
+				let hit = cross_section.points().any(|position| {
+					cursor
+						.get(position)
+						.is_some_and(|block| solid(position, block))
+				});
+
+				if hit {
+					let (face, side) = if normal < 0 {
+						(layer, aabb.max[axis])
+					} else {
+						(layer + 1, aabb.min[axis])
+					};
+
+					motion = F::from(face).unwrap_or_else(crate::lazy_unreachable!()) - side;
+					sweep.normal[axis] = normal;
+
+					break;
+				}
+			}
+
+			aabb.min[axis] += motion;
+			aabb.max[axis] += motion;
+			sweep.motion[axis] = motion;
+		}
+
+		sweep
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	fn world() -> Uniform<Vec<bool>, ct::Uniform<4, 3>, 3> {
+		let mut world = Uniform::default();
+
+		// a floor below `y = 0` and a wall at `x = 3`
+		let floor = Aabb::new(Point::from([-8, -1, -8]), Point::from([8, 0, 8]));
+		let wall = Aabb::new(Point::from([3, 0, -8]), Point::from([4, 4, 8]));
+
+		world.fill_region(floor, missing::Create, true);
+		world.fill_region(wall, missing::Create, true);
+
+		world
+	}
+
+	fn player(x: f32, y: f32, z: f32) -> Aabb<f32, 3> {
+		let min = Point::from([x - 0.25, y, z - 0.25]);
+
+		Aabb::new(min, min + Vector::from([0.5, 1.75, 0.5]))
+	}
+
+	#[test]
+	fn test_sweep() {
+		let world = world();
+		let solid = |_, &solid: &bool| solid;
+
+		// falling onto the floor
+		let sweep = world.sweep(player(0.5, 2.5, 0.5), Vector::from([0.0, -5.0, 0.0]), solid);
+
+		assert_eq!(sweep.motion, Vector::from([0.0, -2.5, 0.0]));
+		assert_eq!(sweep.normal, Vector::from([0, 1, 0]));
+
+		// standing on the floor, sliding along the wall
+		let sweep = world.sweep(player(1.5, 0.0, 0.5), Vector::from([2.0, 0.0, 1.5]), solid);
+
+		assert_eq!(sweep.motion, Vector::from([1.25, 0.0, 1.5]));
+		assert_eq!(sweep.normal, Vector::from([-1, 0, 0]));
+		assert!(sweep.collided());
+
+		// free motion, across `Chunk` boundaries
+		let velocity = Vector::from([-6.0, 3.0, -6.0]);
+		let sweep = world.sweep(player(1.5, 0.0, 0.5), velocity, solid);
+
+		assert_eq!(sweep.motion, velocity);
+		assert!(!sweep.collided());
+
+		// blocks in missing `Chunk`s are not solid
+		let velocity = Vector::from([12.0, 0.0, 0.0]);
+		let sweep = world.sweep(player(0.5, 10.0, 0.5), velocity, |_, _| true);
+
+		assert_eq!(sweep.motion, velocity);
+	}
+}
@@ -0,0 +1,253 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Point;
+use crate::storage::*;
+use crate::Shape;
+
+use std::ptr::NonNull;
+
+/// World space bounds of the `Chunk` at `chunk`
+fn covered<S: Shape<B>, const W: usize, const C: usize, const B: usize>(
+	shape: S,
+	chunk: Point<i32, C>,
+) -> Aabb<i32, W>
+where
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	let bounds = shape.bounds();
+
+	Aabb::new(
+		shape.chunk_block_to_world(chunk, bounds.min),
+		shape.chunk_block_to_world(chunk, bounds.max),
+	)
+}
+
+/// `Chunk` local position of `position` if it is in `covered`
+fn local<S: Shape<B>, const W: usize, const B: usize>(
+	shape: S,
+	covered: Aabb<i32, W>,
+	position: Point<i32, W>,
+) -> Option<Point<i32, B>> {
+	if !covered.contains(position) {
+		return None;
+	}
+
+	let offset = (position - covered.min).resize_generic(math::Const::<B>, math::Const::<1>, 0);
+
+	Some(shape.bounds().min + offset)
+}
+
+/// Reads blocks of a [`Multiform`] remembering the last `Chunk` it resolved
+///
+/// Consecutive reads in the same `Chunk` skip the `Chunk` lookup and the [`Shape::world_to_chunk_block`] conversion
+#[derive(Debug)]
+pub struct Cursor<'a, T, S, const W: usize, const C: usize, const B: usize> {
+	world: &'a Multiform<T, S, W, C, B>,
+	cache: Option<(Aabb<i32, W>, Option<&'a T>)>,
+}
+
+impl<'a, T, S, const W: usize, const C: usize, const B: usize> Cursor<'a, T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	pub fn world(&self) -> &'a Multiform<T, S, W, C, B> {
+		self.world
+	}
+	pub fn get(&mut self, position: Point<i32, W>) -> Option<&'a T::Item> {
+		let shape = self.world.shape;
+
+		let cached = self
+			.cache
+			.and_then(|(covered, storage)| Some((local(shape, covered, position)?, storage)));
+
+		let (block, storage) = match cached {
+			Some(cached) => cached,
+			None => {
+				let (chunk, block) = shape.world_to_chunk_block(position);
+				let storage = self.world.inner.get(chunk);
+
+				self.cache = Some((covered(shape, chunk), storage));
+
+				(block, storage)
+			}
+		};
+
+		storage?.as_slice().get(shape.position_to_index(block)?)
+	}
+}
+
+/// Reads and writes blocks of a [`Multiform`] remembering the last `Chunk` it resolved
+///
+/// Consecutive accesses in the same `Chunk` skip the `Chunk` lookup and the [`Shape::world_to_chunk_block`] conversion.
+/// The last `Chunk` stays in the world, the cursor only borrows it until it moves to another `Chunk`
+#[derive(Debug)]
+pub struct CursorMut<'a, T, S, const W: usize, const C: usize, const B: usize> {
+	world: &'a mut Multiform<T, S, W, C, B>,
+	cache: Option<(Aabb<i32, W>, Option<NonNull<T>>)>,
+}
+
+impl<'a, T, S, const W: usize, const C: usize, const B: usize> CursorMut<'a, T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	fn resolve(&mut self, position: Point<i32, W>) -> Option<(Point<i32, B>, &mut T)> {
+		let shape = self.world.shape;
+
+		let cached = self
+			.cache
+			.and_then(|(covered, storage)| Some((local(shape, covered, position)?, storage)));
+
+		let (block, storage) = match cached {
+			Some(cached) => cached,
+			None => {
+				let (chunk, block) = shape.world_to_chunk_block(position);
+				let storage = self.world.inner.get_mut(chunk).map(NonNull::from);
+
+				self.cache = Some((covered(shape, chunk), storage));
+
+				(block, storage)
+			}
+		};
+
+		// SAFETY: `storage` comes from `get_mut` on `self.world.inner`, which the cursor borrows mutably for `'a`,
+		// so nothing else reaches the `Chunk` while the cursor lives. The cursor itself only touches `inner` again
+		// in `get_mut` above when it moves to another `Chunk`, which replaces the pointer. The returned reference
+		// borrows `self`, so it ends before the next lookup. Covered by the tests of this module under Miri
+		Some((block, unsafe { storage?.as_mut() }))
+	}
+	pub fn get(&mut self, position: Point<i32, W>) -> Option<&T::Item> {
+		self.get_mut(position).map(|block| &*block)
+	}
+	pub fn get_mut(&mut self, position: Point<i32, W>) -> Option<&mut T::Item> {
+		let shape = self.world.shape;
+
+		let (block, storage) = self.resolve(position)?;
+
+		storage
+			.as_mut_slice()
+			.get_mut(shape.position_to_index(block)?)
+	}
+	/// Replaces the block at `position`, returns `block` back if its `Chunk` is missing
+	pub fn replace(&mut self, position: Point<i32, W>, block: T::Item) -> Result<T::Item, T::Item> {
+		match self.get_mut(position) {
+			Some(slot) => Ok(std::mem::replace(slot, block)),
+			None => Err(block),
+		}
+	}
+}
+
+/// # Cursors
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	pub fn cursor(&self) -> Cursor<'_, T, S, W, C, B> {
+		Cursor {
+			world: self,
+			cache: None,
+		}
+	}
+	pub fn cursor_mut(&mut self) -> CursorMut<'_, T, S, W, C, B> {
+		CursorMut {
+			world: self,
+			cache: None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::rt;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_cursor() {
+		let mut world = Uniform::<Vec<i32>, ct::Uniform<4, 2>, 2>::default();
+
+		let region = Aabb::new(Point::from([-8, -8]), Point::from([8, 4]));
+
+		world.fill_with(region, missing::Create, |position| {
+			position.x * 100 + position.y
+		});
+
+		let mut cursor = world.cursor();
+
+		for x in -9..9 {
+			for y in -9..9 {
+				let position = Point::from([x, y]);
+
+				assert_eq!(cursor.get(position), cursor.world().get_block(position));
+			}
+		}
+	}
+
+	#[test]
+	fn test_cursor_mut() {
+		let mut world = Uniform::<Vec<i32>, rt::Bounded<2>, 2>::new(rt::Bounded::centered(
+			math::Vector::from([1, 1]),
+		));
+
+		let region = Aabb::new(Point::from([-4, -4]), Point::from([5, 2]));
+
+		world.fill_region(region, missing::Create, 0);
+
+		let chunks = world.len();
+
+		let mut cursor = world.cursor_mut();
+
+		for position in region.expand(math::Vector::repeat(1)).points() {
+			let inside = region.contains(position);
+
+			assert_eq!(cursor.get(position).is_some(), inside);
+			assert_eq!(
+				cursor.replace(position, position.x - position.y).is_ok(),
+				inside
+			);
+		}
+
+		assert_eq!(cursor.get(Point::from([3, -4])), Some(&7));
+		assert_eq!(world.len(), chunks);
+
+		for position in region.points() {
+			assert_eq!(world.get_block(position), Some(&(position.x - position.y)));
+		}
+	}
+
+	#[test]
+	fn test_cursor_mut_revisit() {
+		let mut world = Uniform::<Vec<i32>, ct::Uniform<2, 2>, 2>::default();
+
+		world.fill_region(
+			Aabb::new(Point::from([0, 0]), Point::from([4, 2])),
+			missing::Create,
+			0,
+		);
+
+		let mut cursor = world.cursor_mut();
+
+		// moving back and forth between two `Chunk`s while writing through each one
+		for round in 1..=3 {
+			for position in [[0, 0], [2, 0], [1, 1], [3, 1]].map(Point::from) {
+				*cursor.get_mut(position).unwrap() += round;
+			}
+		}
+
+		assert_eq!(cursor.get(Point::from([0, 0])), Some(&6));
+		assert_eq!(cursor.replace(Point::from([3, 1]), -1), Ok(6));
+		assert_eq!(cursor.get(Point::from([1, 1])), Some(&6));
+		assert_eq!(world.get_block(Point::from([3, 1])), Some(&-1));
+	}
+}
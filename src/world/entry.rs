@@ -38,7 +38,7 @@ impl<'a, T, S: Shape<B>, const C: usize, const B: usize> Entry<'a, T, S, C, B> {
 			shape,
 		)
 	}
-	pub fn value_mut(&mut self) -> Option<ChunkMut<T, S, B>> {
+	pub fn value_mut(&mut self) -> Option<ChunkMut<'_, T, S, B>> {
 		self.inner
 			.value_mut()
 			.map(|storage| ChunkMut::new(storage, self.shape))
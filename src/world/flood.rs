@@ -0,0 +1,220 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Point;
+use crate::storage::*;
+use crate::Shape;
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+/// Face connected components of the blocks of a [`Multiform`] for which some predicate returns `true`
+///
+/// Created by [`Multiform::connected_components`]
+#[derive(Debug, Clone)]
+pub struct Components<S, const W: usize, const C: usize, const B: usize> {
+	/// Component of every block, `None` for blocks which are not part of any
+	pub labels: Multiform<Vec<Option<usize>>, S, W, C, B>,
+	/// Number of blocks in each component
+	pub sizes: Vec<usize>,
+}
+
+impl<S: Shape<B>, const W: usize, const C: usize, const B: usize> Components<S, W, C, B>
+where
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Component of the block at `position`
+	pub fn label(&self, position: Point<i32, W>) -> Option<usize> {
+		self.labels.get_block(position).copied().flatten()
+	}
+	/// Number of components
+	pub fn len(&self) -> usize {
+		self.sizes.len()
+	}
+	pub fn is_empty(&self) -> bool {
+		self.sizes.is_empty()
+	}
+}
+
+/// # Flood fill
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	T::Item: Clone,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Sets the face connected blocks reachable from `start` for which `predicate` returns `true` to `value`
+	///
+	/// The fill continues across `Chunk` boundaries but not into missing `Chunk`s. Returns the number of written blocks,
+	/// as an `Err` if the fill stopped because it would have written more than `limit` blocks
+	pub fn flood_fill(
+		&mut self,
+		start: Point<i32, W>,
+		mut predicate: impl FnMut(&T::Item) -> bool,
+		value: T::Item,
+		limit: Option<usize>,
+	) -> Result<usize, usize> {
+		let offsets: Vec<_> = math::Neighborhood::VonNeumann(1).offsets().collect();
+
+		let mut cursor = self.cursor_mut();
+
+		let mut visited = HashSet::from([start]);
+		let mut queue = VecDeque::from([start]);
+		let mut filled = 0;
+
+		while let Some(position) = queue.pop_front() {
+			let Some(block) = cursor.get_mut(position) else {
+				continue;
+			};
+
+			if !predicate(block) {
+				continue;
+			}
+
+			if limit == Some(filled) {
+				return Err(filled);
+			}
+
+			*block = value.clone();
+			filled += 1;
+
+			for &offset in &offsets {
+				if visited.insert(position + offset) {
+					queue.push_back(position + offset);
+				}
+			}
+		}
+
+		Ok(filled)
+	}
+}
+
+/// # Connected components
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Labels the face connected components of the blocks for which `predicate` returns `true`
+	///
+	/// Components are numbered in the order in which their first block is found when iterating over the `Chunk`s
+	pub fn connected_components(
+		&self,
+		mut predicate: impl FnMut(&T::Item) -> bool,
+	) -> Components<S, W, C, B> {
+		let shape = self.shape;
+		let offsets: Vec<_> = math::Neighborhood::VonNeumann(1).offsets().collect();
+
+		let mut labels = Multiform::new(shape);
+
+		for chunk in self.positions() {
+			labels.inner.insert(chunk, vec![None; shape.capacity()]);
+		}
+
+		let mut cursor = self.cursor();
+		let mut sizes = Vec::new();
+		let mut queue = VecDeque::new();
+
+		for (chunk, storage) in self.inner.iter() {
+			for (index, block) in storage.as_slice().iter().enumerate() {
+				let labeled = labels
+					.inner
+					.get(chunk)
+					.and_then(|labels| labels[index])
+					.is_some();
+
+				if labeled || !predicate(block) {
+					continue;
+				}
+
+				let label = sizes.len();
+				let mut size = 0;
+
+				let block = shape
+					.index_to_position(index)
+					.unwrap_or_else(crate::lazy_unreachable!());
+				let start = shape.chunk_block_to_world(chunk, block);
+
+				labels
+					.inner
+					.get_mut(chunk)
+					.unwrap_or_else(crate::lazy_unreachable!())[index] = Some(label);
+				queue.push_back(start);
+
+				while let Some(position) = queue.pop_front() {
+					size += 1;
+
+					for &offset in &offsets {
+						let neighbor = position + offset;
+
+						let Some(slot @ None) = labels.get_block_mut(neighbor) else {
+							continue;
+						};
+
+						if cursor.get(neighbor).is_some_and(&mut predicate) {
+							*slot = Some(label);
+
+							queue.push_back(neighbor);
+						}
+					}
+				}
+
+				sizes.push(size);
+			}
+		}
+
+		Components { labels, sizes }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::Aabb;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_flood_fill() {
+		let mut world = Uniform::<Vec<u8>, ct::Uniform<4, 2>, 2>::default();
+
+		world.fill_region(
+			Aabb::new(Point::from([-4, -4]), Point::from([8, 8])),
+			missing::Create,
+			0,
+		);
+		world.draw_line(
+			math::Bresenham::new(Point::from([2, -4]), Point::from([2, 7])),
+			missing::Skip,
+			1,
+		);
+
+		let components = world.connected_components(|&block| block == 0);
+
+		assert_eq!(components.sizes, [72, 60]);
+		assert_eq!(components.label(Point::from([0, 0])), Some(0));
+		assert_eq!(components.label(Point::from([7, 7])), Some(1));
+		assert_eq!(components.label(Point::from([2, 0])), None);
+
+		let empty = |&block: &u8| block == 0;
+
+		assert_eq!(
+			world.flood_fill(Point::from([0, 0]), empty, 2, Some(10)),
+			Err(10)
+		);
+		assert_eq!(
+			world.flood_fill(Point::from([0, 0]), |&block| block != 1, 2, Some(72)),
+			Ok(72)
+		);
+		assert_eq!(
+			world.flood_fill(Point::from([0, 0]), |_| true, 3, None),
+			Ok(144)
+		);
+		assert_eq!(world.get_block(Point::from([7, 7])), Some(&3));
+	}
+}
@@ -0,0 +1,806 @@
+use crate::chunk::Chunk;
+use crate::chunk::ChunkMut;
+use crate::chunk::ChunkRef;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::PositionMap;
+use crate::Shape;
+use crate::UniformWorldCoordinate;
+
+/// Child of an internal node of a [`Hierarchical`] world
+#[derive(Clone, PartialEq, Eq)]
+enum Node<T, I> {
+	/// Every block of the `Chunk` has the same value
+	Tile(I),
+	Leaf(T),
+}
+
+/// Entry of the root map of a [`Hierarchical`] world
+#[derive(Clone, PartialEq, Eq)]
+enum Root<T, I> {
+	/// Every block of the `N^D` `Chunk`s has the same value
+	Tile(I),
+	/// `N^D` optional children, the first axis varying fastest
+	Internal(Box<[Option<Node<T, I>>]>),
+}
+
+impl<T, I> Root<T, I> {
+	/// Number of leaf `Chunk`s and of `Chunk`s in tiles, out of `chunks` in the node
+	fn count(&self, chunks: usize) -> (usize, usize) {
+		match self {
+			Root::Tile(_) => (0, chunks),
+			Root::Internal(children) => {
+				children
+					.iter()
+					.fold((0, 0), |(leaves, tiles), child| match child {
+						Some(Node::Leaf(_)) => (leaves + 1, tiles),
+						Some(Node::Tile(_)) => (leaves, tiles + 1),
+						None => (leaves, tiles),
+					})
+			}
+		}
+	}
+}
+
+/// `D` dimensional world stored as a tree of a root map, internal nodes and leaf `Chunk`s, as in VDB
+///
+/// Every entry of the root map covers `N^D` `Chunk`s and is either an internal node or a tile, which gives all of
+/// its blocks the same value of type `I`. Every child of an internal node is missing, a leaf `Chunk` of shape `S`
+/// or a tile. Blocks in tiles read as the value of the tile, writing to them splits the tile, see [`Self::prune`]
+pub struct Hierarchical<T, S, const D: usize, const N: usize, I = <T as Storage>::Item> {
+	root: PositionMap<Root<T, I>, D>,
+	shape: S,
+	leaves: usize,
+	tiles: usize,
+}
+
+impl<T, S, const D: usize, const N: usize, I> Hierarchical<T, S, D, N, I>
+where
+	S: Shape<D>,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	/// Number of `Chunk`s covered by a root node
+	const CHUNKS: usize = N.pow(D as u32);
+
+	pub fn new(shape: S) -> Self {
+		assert!(N > 0, "internal nodes have no children");
+
+		Self {
+			root: PositionMap::new(),
+			shape,
+			leaves: 0,
+			tiles: 0,
+		}
+	}
+	pub fn shape(&self) -> &S {
+		&self.shape
+	}
+	/// Number of `Chunk`s, leaves and `Chunk`s in tiles
+	pub fn len(&self) -> usize {
+		self.leaves + self.tiles
+	}
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+	/// Number of leaf `Chunk`s
+	pub fn leaves(&self) -> usize {
+		self.leaves
+	}
+	pub fn world_to_chunk_block(&self, position: Point<i32, D>) -> UniformWorldCoordinate<i32, D> {
+		self.shape.world_to_chunk_block(position)
+	}
+	/// Root node containing the `Chunk` at `chunk` and the index of the `Chunk` in it
+	fn split(chunk: Point<i32, D>) -> (Point<i32, D>, usize) {
+		let n = N as i32;
+		let root = chunk.map(|c| c.div_euclid(n));
+		let local = chunk - root.coords * n;
+
+		let index = local
+			.iter()
+			.rev()
+			.fold(0, |index, &c| index * N + c as usize);
+
+		(root, index)
+	}
+	/// `Chunk` at `index` in the root node at `root`
+	fn join(root: Point<i32, D>, index: usize) -> Point<i32, D> {
+		let local = Vector::from_fn(|axis, _| (index / N.pow(axis as u32) % N) as i32);
+
+		root * N as i32 + local
+	}
+	/// World space bounds of the `size^D` `Chunk`s starting at `chunk`
+	fn covered(&self, chunk: Point<i32, D>, size: i32) -> Aabb<i32, D> {
+		let bounds = self.shape.bounds();
+
+		Aabb::new(
+			self.shape.chunk_block_to_world(chunk, bounds.min),
+			self.shape
+				.chunk_block_to_world(chunk + Vector::repeat(size - 1), bounds.max),
+		)
+	}
+	/// Every leaf `Chunk`
+	pub fn iter(&self) -> impl Iterator<Item = (Point<i32, D>, ChunkRef<'_, T, S, D>)> {
+		let shape = self.shape;
+
+		self.root
+			.iter()
+			.filter_map(|(root, node)| match node {
+				Root::Internal(children) => Some((root, children)),
+				Root::Tile(_) => None,
+			})
+			.flat_map(move |(root, children)| {
+				children
+					.iter()
+					.enumerate()
+					.filter_map(move |(index, child)| match child {
+						Some(Node::Leaf(leaf)) => {
+							Some((Self::join(root, index), ChunkRef::new(leaf, shape)))
+						}
+						_ => None,
+					})
+			})
+	}
+	/// Positions of the leaf `Chunk`s
+	pub fn positions(&self) -> impl '_ + Iterator<Item = Point<i32, D>> {
+		self.iter().map(|(position, _)| position)
+	}
+	/// World space bounds and value of every tile
+	pub fn tiles(&self) -> impl '_ + Iterator<Item = (Aabb<i32, D>, &I)> {
+		self.root.iter().flat_map(move |(root, node)| {
+			let tiles: Box<dyn Iterator<Item = _>> = match node {
+				Root::Tile(value) => Box::new(std::iter::once((
+					self.covered(root * N as i32, N as i32),
+					value,
+				))),
+				Root::Internal(children) => Box::new(children.iter().enumerate().filter_map(
+					move |(index, child)| match child {
+						Some(Node::Tile(value)) => {
+							Some((self.covered(Self::join(root, index), 1), value))
+						}
+						_ => None,
+					},
+				)),
+			};
+
+			tiles
+		})
+	}
+	/// The leaf `Chunk` at `position`, `None` if it is missing or part of a tile, see [`Self::tile`]
+	pub fn leaf(&self, position: Point<i32, D>) -> Option<ChunkRef<'_, T, S, D>> {
+		let (root, index) = Self::split(position);
+
+		match self.root.get(root)? {
+			Root::Internal(children) => match children[index].as_ref()? {
+				Node::Leaf(leaf) => Some(ChunkRef::new(leaf, self.shape)),
+				Node::Tile(_) => None,
+			},
+			Root::Tile(_) => None,
+		}
+	}
+	/// Mutable access to the leaf `Chunk` at `position`, `None` if it is missing or part of a tile
+	pub fn leaf_mut(&mut self, position: Point<i32, D>) -> Option<ChunkMut<'_, T, S, D>> {
+		let (root, index) = Self::split(position);
+
+		match self.root.get_mut(root)? {
+			Root::Internal(children) => match children[index].as_mut()? {
+				Node::Leaf(leaf) => Some(ChunkMut::new(leaf, self.shape)),
+				Node::Tile(_) => None,
+			},
+			Root::Tile(_) => None,
+		}
+	}
+	/// Value of the tile covering the `Chunk` at `position`, `None` if it is missing or a leaf
+	pub fn tile(&self, position: Point<i32, D>) -> Option<&I> {
+		let (root, index) = Self::split(position);
+
+		match self.root.get(root)? {
+			Root::Internal(children) => match children[index].as_ref()? {
+				Node::Tile(value) => Some(value),
+				Node::Leaf(_) => None,
+			},
+			Root::Tile(value) => Some(value),
+		}
+	}
+}
+
+/// # Block access
+impl<T, S, const D: usize, const N: usize> Hierarchical<T, S, D, N>
+where
+	T: ContiguousMemory,
+	S: Shape<D>,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	/// The leaf `Chunk` at `position` as in [`Multiform::chunk`](super::Multiform::chunk)
+	///
+	/// Tiles have no storage to borrow, so this is `None` for a `Chunk` in a tile even though [`Self::get_block`]
+	/// reads its blocks, see [`Self::tile`]
+	pub fn chunk(&self, position: Point<i32, D>) -> Option<ChunkRef<'_, T, S, D>> {
+		self.leaf(position)
+	}
+	/// The block at `block` in the leaf or tile `Chunk` at `chunk`
+	pub fn block(&self, chunk: Point<i32, D>, block: Point<i32, D>) -> Option<&T::Item> {
+		let index = self.shape.position_to_index(block)?;
+
+		if let Some(value) = self.tile(chunk) {
+			return Some(value);
+		}
+
+		self.leaf(chunk)?.storage.as_slice().get(index)
+	}
+	pub fn get_block(&self, position: Point<i32, D>) -> Option<&T::Item> {
+		let (chunk, block) = self.world_to_chunk_block(position);
+
+		self.block(chunk, block)
+	}
+}
+
+/// # Tile splitting
+impl<T, S, const D: usize, const N: usize> Hierarchical<T, S, D, N>
+where
+	T: ContiguousMemoryMut + FromFn,
+	T::Item: Clone,
+	S: Shape<D>,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	/// Children of the root node at `root`, splitting it if it is a tile
+	fn split_root(root: &mut Root<T, T::Item>) -> &mut [Option<Node<T, T::Item>>] {
+		if let Root::Tile(value) = root {
+			let children = (0..Self::CHUNKS)
+				.map(|_| Some(Node::Tile(value.clone())))
+				.collect();
+
+			*root = Root::Internal(children);
+		}
+
+		match root {
+			Root::Internal(children) => children,
+			Root::Tile(_) => unreachable!(),
+		}
+	}
+	/// Storage of the `Chunk` `node`, splitting it if it is a tile
+	fn split_leaf<'a>(
+		shape: S,
+		(leaves, tiles): (&mut usize, &mut usize),
+		node: &'a mut Node<T, T::Item>,
+	) -> &'a mut T {
+		if let Node::Tile(value) = node {
+			let leaf = T::from_fn(shape.capacity(), |_| value.clone());
+
+			*node = Node::Leaf(leaf);
+			*leaves += 1;
+			*tiles -= 1;
+		}
+
+		match node {
+			Node::Leaf(leaf) => leaf,
+			Node::Tile(_) => unreachable!(),
+		}
+	}
+	/// Mutable access to the `Chunk` at `position` as in [`Multiform::chunk_mut`](super::Multiform::chunk_mut),
+	/// splitting the tile covering it into a leaf
+	pub fn chunk_mut(&mut self, position: Point<i32, D>) -> Option<ChunkMut<'_, T, S, D>> {
+		let shape = self.shape;
+		let (root, index) = Self::split(position);
+
+		let children = Self::split_root(self.root.get_mut(root)?);
+		let counts = (&mut self.leaves, &mut self.tiles);
+		let leaf = Self::split_leaf(shape, counts, children[index].as_mut()?);
+
+		Some(ChunkMut::new(leaf, shape))
+	}
+	/// Mutable access to the block at `block` in the `Chunk` at `chunk`, splitting the tile covering it
+	pub fn block_mut(
+		&mut self,
+		chunk: Point<i32, D>,
+		block: Point<i32, D>,
+	) -> Option<&mut T::Item> {
+		let index = self.shape.position_to_index(block)?;
+
+		self.chunk_mut(chunk)?.storage.as_mut_slice().get_mut(index)
+	}
+	/// Mutable access to the block at `position`, splitting the tile covering it
+	pub fn get_block_mut(&mut self, position: Point<i32, D>) -> Option<&mut T::Item> {
+		let (chunk, block) = self.world_to_chunk_block(position);
+
+		self.block_mut(chunk, block)
+	}
+	/// Replaces the `Chunk` at `position` with a leaf, returns the previous leaf
+	///
+	/// # Panics
+	/// This function panics if `chunk.shape != self.shape`
+	pub fn insert(&mut self, position: Point<i32, D>, chunk: Chunk<T, S, D>) -> Option<T> {
+		let (shape, storage) = chunk.into_raw_parts();
+
+		assert!(shape == self.shape);
+
+		let (root, index) = Self::split(position);
+
+		let root = self
+			.root
+			.entry(root)
+			.or_insert_with(|| Root::Internal((0..Self::CHUNKS).map(|_| None).collect()));
+
+		match Self::split_root(root)[index].replace(Node::Leaf(storage)) {
+			Some(Node::Leaf(leaf)) => return Some(leaf),
+			Some(Node::Tile(_)) => self.tiles -= 1,
+			None => {}
+		}
+
+		self.leaves += 1;
+
+		None
+	}
+	/// Removes the leaf or tile `Chunk` at `position`, returns it if it was a leaf
+	pub fn remove(&mut self, position: Point<i32, D>) -> Option<Chunk<T, S, D>> {
+		let (root, index) = Self::split(position);
+
+		let children = Self::split_root(self.root.get_mut(root)?);
+		let removed = children[index].take();
+
+		if children.iter().all(Option::is_none) {
+			self.root.remove(root);
+		}
+
+		match removed? {
+			Node::Leaf(leaf) => {
+				self.leaves -= 1;
+
+				Some(Chunk::new(leaf, self.shape))
+			}
+			Node::Tile(_) => {
+				self.tiles -= 1;
+
+				None
+			}
+		}
+	}
+	/// Entry of the `Chunk` at `position`, tiles are occupied and split into leaves when accessed
+	pub fn entry(&mut self, position: Point<i32, D>) -> HierarchicalEntry<'_, T, S, D, N> {
+		HierarchicalEntry {
+			world: self,
+			position,
+		}
+	}
+	/// Sets every block in `region` to `value`
+	///
+	/// Root nodes and `Chunk`s which are completely inside of `region` become tiles, missing `Chunk`s which are
+	/// partially inside of it are created with default blocks
+	pub fn fill_region(&mut self, region: Aabb<i32, D>, value: T::Item)
+	where
+		T::Item: Default,
+	{
+		if region.is_empty() {
+			return;
+		}
+
+		let shape = self.shape;
+		let n = N as i32;
+
+		let chunks = Aabb::new(
+			self.shape.world_to_chunk(region.min),
+			self.shape.world_to_chunk(region.max - Vector::repeat(1)) + Vector::repeat(1),
+		);
+		let roots = Aabb::new(
+			chunks.min.map(|c| c.div_euclid(n)),
+			(chunks.max - Vector::repeat(1)).map(|c| c.div_euclid(n)) + Vector::repeat(1),
+		);
+
+		for root in roots.points() {
+			if region.contains_aabb(&self.covered(root * n, n)) {
+				if let Some(previous) = self.root.insert(root, Root::Tile(value.clone())) {
+					let (leaves, tiles) = previous.count(Self::CHUNKS);
+
+					self.leaves -= leaves;
+					self.tiles -= tiles;
+				}
+
+				self.tiles += Self::CHUNKS;
+
+				continue;
+			}
+
+			let root_chunks = Aabb::new(root * n, root * n + Vector::repeat(n));
+			let covered: Vec<_> = root_chunks
+				.intersection(&chunks)
+				.into_iter()
+				.flat_map(|part| part.points())
+				.map(|chunk| (chunk, self.covered(chunk, 1)))
+				.collect();
+
+			let root = self
+				.root
+				.entry(root)
+				.or_insert_with(|| Root::Internal((0..Self::CHUNKS).map(|_| None).collect()));
+			let children = Self::split_root(root);
+
+			for (chunk, covered) in covered {
+				let (_, index) = Self::split(chunk);
+
+				if region.contains_aabb(&covered) {
+					match children[index].replace(Node::Tile(value.clone())) {
+						Some(Node::Leaf(_)) => self.leaves -= 1,
+						Some(Node::Tile(_)) => self.tiles -= 1,
+						None => {}
+					}
+
+					self.tiles += 1;
+
+					continue;
+				}
+
+				let node = children[index].get_or_insert_with(|| {
+					self.tiles += 1;
+
+					Node::Tile(T::Item::default())
+				});
+				let counts = (&mut self.leaves, &mut self.tiles);
+				let slice = Self::split_leaf(shape, counts, node).as_mut_slice();
+
+				for position in region
+					.intersection(&covered)
+					.into_iter()
+					.flat_map(|part| part.points())
+				{
+					let index = shape
+						.position_to_index(shape.world_to_block(position))
+						.unwrap_or_else(crate::lazy_unreachable!());
+
+					slice[index] = value.clone();
+				}
+			}
+		}
+	}
+	/// Merges leaf `Chunk`s whose blocks are all equal into tiles and root nodes whose children are all equal tiles
+	/// into a single tile, root nodes without any children are removed
+	pub fn prune(&mut self)
+	where
+		T::Item: PartialEq,
+	{
+		let mut empty = Vec::new();
+
+		for (position, root) in self.root.iter_mut() {
+			let Root::Internal(children) = root else {
+				continue;
+			};
+
+			for child in children.iter_mut() {
+				let Some(Node::Leaf(leaf)) = child else {
+					continue;
+				};
+
+				let slice = leaf.as_slice();
+				let uniform = slice
+					.first()
+					.filter(|&first| slice.iter().all(|block| block == first))
+					.cloned();
+
+				if let Some(value) = uniform {
+					*child = Some(Node::Tile(value));
+					self.leaves -= 1;
+					self.tiles += 1;
+				}
+			}
+
+			if children.iter().all(Option::is_none) {
+				empty.push(position);
+
+				continue;
+			}
+
+			let uniform = match &children[0] {
+				Some(Node::Tile(first)) => children
+					.iter()
+					.all(|child| matches!(child, Some(Node::Tile(value)) if value == first))
+					.then(|| first.clone()),
+				_ => None,
+			};
+
+			if let Some(value) = uniform {
+				*root = Root::Tile(value);
+			}
+		}
+
+		for position in empty {
+			self.root.remove(position);
+		}
+	}
+}
+
+/// Entry of a `Chunk` of a [`Hierarchical`] world, see [`Hierarchical::entry`]
+pub struct HierarchicalEntry<'a, T, S, const D: usize, const N: usize, I = <T as Storage>::Item> {
+	world: &'a mut Hierarchical<T, S, D, N, I>,
+	position: Point<i32, D>,
+}
+
+impl<'a, T, S, const D: usize, const N: usize> HierarchicalEntry<'a, T, S, D, N>
+where
+	T: ContiguousMemoryMut + FromFn,
+	T::Item: Clone,
+	S: Shape<D>,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	pub fn position(&self) -> Point<i32, D> {
+		self.position
+	}
+	pub fn and_modify<F>(mut self, f: F) -> Self
+	where
+		F: FnOnce(ChunkMut<T, S, D>),
+	{
+		if let Some(chunk) = self.value_mut() {
+			f(chunk);
+		}
+
+		self
+	}
+	pub fn value_mut(&mut self) -> Option<ChunkMut<'_, T, S, D>> {
+		self.world.chunk_mut(self.position)
+	}
+	pub fn or_insert(self, default: T) -> ChunkMut<'a, T, S, D> {
+		self.or_insert_with(|| default)
+	}
+	pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> ChunkMut<'a, T, S, D> {
+		let Self { world, position } = self;
+
+		if world.leaf(position).is_none() && world.tile(position).is_none() {
+			world.insert(position, Chunk::new(default(), world.shape));
+		}
+
+		world
+			.chunk_mut(position)
+			.unwrap_or_else(crate::lazy_unreachable!())
+	}
+	pub fn or_default(self) -> ChunkMut<'a, T, S, D>
+	where
+		T::Item: Default,
+	{
+		let capacity = self.world.shape.capacity();
+
+		self.or_insert_with(|| T::from_fn(capacity, |_| Default::default()))
+	}
+}
+
+impl<T, S, const D: usize, const N: usize, I> Eq for Hierarchical<T, S, D, N, I>
+where
+	T: Eq,
+	S: Eq,
+	I: Eq,
+{
+}
+
+/// Equal if the trees are equal, a tile is not equal to a leaf with the same blocks
+impl<T, S, const D: usize, const N: usize, I> PartialEq for Hierarchical<T, S, D, N, I>
+where
+	T: PartialEq,
+	S: PartialEq,
+	I: PartialEq,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.shape == other.shape && self.root == other.root
+	}
+}
+
+impl<T, S, const D: usize, const N: usize, I> Clone for Hierarchical<T, S, D, N, I>
+where
+	T: Clone,
+	S: Clone,
+	I: Clone,
+{
+	fn clone(&self) -> Self {
+		Self {
+			root: self.root.clone(),
+			shape: self.shape.clone(),
+			leaves: self.leaves,
+			tiles: self.tiles,
+		}
+	}
+}
+
+const _: () = {
+	use std::fmt::*;
+
+	impl<T, S, const D: usize, const N: usize, I> Debug for Hierarchical<T, S, D, N, I>
+	where
+		S: Debug,
+	{
+		fn fmt(&self, f: &mut Formatter) -> Result {
+			f.debug_struct("Hierarchical")
+				.field("roots", &self.root.len())
+				.field("leaves", &self.leaves)
+				.field("tiles", &self.tiles)
+				.field("shape", &self.shape)
+				.finish_non_exhaustive()
+		}
+	}
+
+	impl<'a, T, S, const D: usize, const N: usize, I> Debug for HierarchicalEntry<'a, T, S, D, N, I> {
+		fn fmt(&self, f: &mut Formatter) -> Result {
+			f.debug_struct("HierarchicalEntry")
+				.field("position", &self.position)
+				.finish_non_exhaustive()
+		}
+	}
+};
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+
+	type World = Hierarchical<Vec<u8>, ct::Uniform<4, 3>, 3, 4>;
+
+	/// Volume of every tile and leaf `Chunk`
+	fn region_chunks_volume(world: &World) -> i32 {
+		world.tiles().map(|(aabb, _)| aabb.volume()).sum::<i32>() + world.leaves() as i32 * 64
+	}
+
+	#[test]
+	fn test_tiles() {
+		let mut world = World::new(ct::Uniform);
+
+		// root nodes span 16 blocks, `Chunk`s 4
+		let region = Aabb::new(Point::from([-100; 3]), Point::from([100; 3]));
+
+		world.fill_region(region, 1);
+
+		let tiles = world.tiles().count();
+
+		assert_eq!(world.leaves(), 0);
+		assert_eq!(world.len() as i32 * 64, region_chunks_volume(&world));
+		assert!(!world.is_empty());
+		assert_eq!(
+			world.tiles().map(|(aabb, _)| aabb.volume()).sum::<i32>(),
+			region.volume()
+		);
+		assert_eq!(world.get_block(Point::from([-100, 0, 99])), Some(&1));
+		assert_eq!(world.get_block(Point::from([-101, 0, 0])), None);
+		assert!(world.leaf(Point::from([0; 3])).is_none());
+		assert_eq!(world.tile(Point::from([0; 3])), Some(&1));
+
+		*world.get_block_mut(Point::from([3, 5, 7])).unwrap() = 2;
+
+		assert_eq!(world.leaves(), 1);
+		assert_eq!(
+			world.positions().collect::<Vec<_>>(),
+			[Point::from([0, 1, 1])]
+		);
+		assert_eq!(world.get_block(Point::from([3, 5, 7])), Some(&2));
+		assert_eq!(world.get_block(Point::from([4, 5, 7])), Some(&1));
+		assert_eq!(world.get_block(Point::from([3, 5, 8])), Some(&1));
+
+		world.prune();
+
+		assert_eq!(world.leaves(), 1);
+
+		*world.get_block_mut(Point::from([3, 5, 7])).unwrap() = 1;
+		world.prune();
+
+		assert_eq!(world.leaves(), 0);
+		assert_eq!(world.tiles().count(), tiles);
+	}
+
+	#[test]
+	fn test_leaves() {
+		let mut world = World::new(ct::Uniform);
+
+		// sparse over millions of blocks per axis
+		for x in [-3_000_000, 0, 3_000_000] {
+			world.fill_region(
+				Aabb::new(Point::from([x + 1, 1, 1]), Point::from([x + 9, 9, 9])),
+				7,
+			);
+		}
+
+		assert_eq!(world.leaves(), 3 * 26);
+		assert_eq!(world.tiles().count(), 3);
+		assert_eq!(world.get_block(Point::from([-3_000_000, 1, 1])), Some(&0));
+		assert_eq!(world.get_block(Point::from([-2_999_999, 1, 1])), Some(&7));
+		assert_eq!(world.get_block(Point::from([3_000_008, 8, 8])), Some(&7));
+		assert_eq!(world.get_block(Point::from([3_000_009, 8, 8])), Some(&0));
+		assert_eq!(world.get_block(Point::from([1_000_000, 5, 5])), None);
+
+		let chunk = world.remove(Point::from([0; 3])).unwrap();
+
+		assert_eq!(chunk.block(Point::from([1; 3])), Some(&7));
+		assert_eq!(world.get_block(Point::from([1; 3])), None);
+		assert!(world.insert(Point::from([0; 3]), chunk).is_none());
+		assert_eq!(world.get_block(Point::from([1; 3])), Some(&7));
+
+		// removing the last `Chunk` of a root node removes it
+		let mut world = World::new(ct::Uniform);
+
+		world.insert(Point::from([5, 5, 5]), crate::Chunk::default());
+		world.remove(Point::from([5, 5, 5]));
+
+		assert!(world.is_empty());
+	}
+
+	#[test]
+	fn test_entry() {
+		let mut world = World::new(ct::Uniform);
+
+		world.fill_region(Aabb::new(Point::from([0; 3]), Point::from([16, 4, 4])), 3);
+
+		let copy = world.clone();
+
+		assert_eq!(world, copy);
+		assert_eq!(world.leaves(), 0);
+		assert_eq!(
+			world.block(Point::from([2, 0, 0]), Point::from([1; 3])),
+			Some(&3)
+		);
+
+		// tiles are occupied
+		world.entry(Point::from([1, 0, 0])).or_default().storage[0] = 5;
+		world
+			.entry(Point::from([3, 0, 0]))
+			.and_modify(|chunk| chunk.storage[0] = 5)
+			.or_insert_with(|| unreachable!());
+
+		assert_eq!(world.leaves(), 2);
+		assert_ne!(world, copy);
+		assert_eq!(
+			world.block(Point::from([1, 0, 0]), Point::from([0; 3])),
+			Some(&5)
+		);
+		assert_eq!(
+			world.block(Point::from([1, 0, 0]), Point::from([1; 3])),
+			Some(&3)
+		);
+		assert_eq!(world.get_block(Point::from([12, 0, 0])), Some(&5));
+		assert!(world.entry(Point::from([9, 0, 0])).value_mut().is_none());
+
+		world.entry(Point::from([9, 0, 0])).or_default();
+
+		assert_eq!(world.leaves(), 3);
+		assert_eq!(world.positions().count(), world.leaves());
+
+		*world
+			.block_mut(Point::from([1, 0, 0]), Point::from([0; 3]))
+			.unwrap() = 3;
+		world.prune();
+
+		assert_eq!(world.leaves(), 1);
+		assert_eq!(world.positions().count(), world.leaves());
+
+		// overwriting leaves with tiles
+		world.fill_region(Aabb::new(Point::from([-16; 3]), Point::from([48; 3])), 0);
+
+		assert_eq!(world.leaves(), 0);
+		assert_eq!(world.positions().count(), 0);
+		assert_eq!(world.len() as i32 * 64, region_chunks_volume(&world));
+
+		loop {
+			let Some(chunk) = world.tiles().map(|(aabb, _)| aabb.min / 4).next() else {
+				break;
+			};
+
+			world.remove(chunk);
+		}
+
+		assert!(world.is_empty());
+	}
+
+	#[test]
+	fn test_array_storage() {
+		let mut world = Hierarchical::<[u8; 64], ct::Uniform<4, 3>, 3, 4>::new(ct::Uniform);
+
+		world.fill_region(Aabb::new(Point::from([-1; 3]), Point::from([17; 3])), 2);
+
+		assert_eq!(world.get_block(Point::from([8; 3])), Some(&2));
+		assert_eq!(world.get_block(Point::from([-1; 3])), Some(&2));
+		assert_eq!(world.get_block(Point::from([-2; 3])), Some(&0));
+
+		*world.get_block_mut(Point::from([8; 3])).unwrap() = 3;
+
+		assert_eq!(world.chunk(Point::from([2; 3])).unwrap().storage[0], 3);
+
+		world.fill_region(Aabb::new(Point::from([8; 3]), Point::from([12; 3])), 2);
+		world.prune();
+
+		assert_eq!(world.get_block(Point::from([8; 3])), Some(&2));
+		assert_eq!(world.len(), world.clone().len());
+	}
+}
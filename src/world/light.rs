@@ -0,0 +1,610 @@
+use super::Cursor;
+use super::Multiform;
+use crate::math;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::Shape;
+
+use std::collections::VecDeque;
+
+/// Light levels of a block
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+	feature = "serde-serialize",
+	derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct Light {
+	/// Light emitted by blocks
+	pub block: u8,
+	/// Light coming from above the world
+	pub sky: u8,
+}
+
+impl Light {
+	/// Default maximum light level
+	pub const MAX: u8 = 15;
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Channel {
+	Block,
+	Sky,
+}
+
+impl Light {
+	fn get(self, channel: Channel) -> u8 {
+		match channel {
+			Channel::Block => self.block,
+			Channel::Sky => self.sky,
+		}
+	}
+	fn set(&mut self, channel: Channel, level: u8) {
+		match channel {
+			Channel::Block => self.block = level,
+			Channel::Sky => self.sky = level,
+		}
+	}
+}
+
+/// Queue based propagation of block and sky [`Light`] through the blocks of a [`Multiform`]
+///
+/// Light loses `max(1, opacity(block))` levels for each block it enters. Sky light at the maximum level travels down
+/// the `up` axis through blocks with no opacity without losing any. Blocks which have no block above them,
+/// because the `Chunk` above is missing, receive sky light
+///
+/// Light is stored for the `Chunk`s of the world at the last [`LightEngine::relight`], `Chunk`s inserted into or
+/// removed from the world afterwards have to be passed to [`LightEngine::update_chunk`]
+pub struct LightEngine<S, O, E, const W: usize, const C: usize, const B: usize> {
+	light: Multiform<Vec<Light>, S, W, C, B>,
+	rules: Rules<O, E>,
+}
+
+/// Everything but the stored light, so that the light can be borrowed on its own
+struct Rules<O, E> {
+	opacity: O,
+	emission: E,
+	up: usize,
+	max: u8,
+}
+
+impl<S, O, E, const W: usize, const C: usize, const B: usize> LightEngine<S, O, E, W, C, B>
+where
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Light engine for worlds with `Chunk`s of `shape` in which `up` is the axis pointing to the sky
+	///
+	/// # Panics
+	/// This function panics if `up >= W`
+	pub fn new(shape: S, up: usize, opacity: O, emission: E) -> Self {
+		assert!(up < W, "`up` is not an axis");
+
+		Self {
+			light: Multiform::new(shape),
+			rules: Rules {
+				opacity,
+				emission,
+				up,
+				max: Light::MAX,
+			},
+		}
+	}
+	/// Sets the maximum light level, levels above it are never produced
+	pub fn with_max(mut self, max: u8) -> Self {
+		self.rules.max = max;
+
+		self
+	}
+	pub fn max(&self) -> u8 {
+		self.rules.max
+	}
+	/// The light levels of every lit `Chunk`
+	pub fn light(&self) -> &Multiform<Vec<Light>, S, W, C, B> {
+		&self.light
+	}
+	pub fn get(&self, position: Point<i32, W>) -> Option<Light> {
+		self.light.get_block(position).copied()
+	}
+}
+
+impl<O, E> Rules<O, E> {
+	fn offset<const W: usize>(&self, sign: i32) -> Vector<i32, W> {
+		let mut offset = Vector::zeros();
+
+		offset[self.up] = sign;
+
+		offset
+	}
+	/// Level `level` becomes after moving by `offset` into `block`
+	fn propagate<I, const W: usize>(
+		&self,
+		channel: Channel,
+		level: u8,
+		block: &I,
+		offset: Vector<i32, W>,
+	) -> u8
+	where
+		O: Fn(&I) -> u8,
+	{
+		let opacity = (self.opacity)(block);
+
+		if channel == Channel::Sky
+			&& level == self.max
+			&& opacity == 0
+			&& offset == self.offset::<W>(-1)
+		{
+			return self.max;
+		}
+
+		level.saturating_sub(opacity.max(1))
+	}
+	/// Level the block at `position` has on its own
+	fn source<T, S, const W: usize, const C: usize, const B: usize>(
+		&self,
+		world: &mut Cursor<T, S, W, C, B>,
+		channel: Channel,
+		position: Point<i32, W>,
+		block: &T::Item,
+	) -> u8
+	where
+		T: ContiguousMemory,
+		S: Shape<B>,
+		O: Fn(&T::Item) -> u8,
+		E: Fn(&T::Item) -> u8,
+		math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+		math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	{
+		match channel {
+			Channel::Block => (self.emission)(block).min(self.max),
+			Channel::Sky if world.get(position + self.offset(1)).is_none() => {
+				self.max.saturating_sub((self.opacity)(block))
+			}
+			Channel::Sky => 0,
+		}
+	}
+}
+
+impl<S, O, E, const W: usize, const C: usize, const B: usize> LightEngine<S, O, E, W, C, B>
+where
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Spreads the light of the blocks in `queue` as far as it reaches
+	fn increase<T>(
+		&mut self,
+		world: &Multiform<T, S, W, C, B>,
+		channel: Channel,
+		mut queue: VecDeque<Point<i32, W>>,
+	) where
+		T: ContiguousMemory,
+		O: Fn(&T::Item) -> u8,
+	{
+		let offsets: Vec<_> = math::Neighborhood::VonNeumann(1).offsets().collect();
+
+		let Self { light, rules } = self;
+
+		let mut blocks = world.cursor();
+		let mut light = light.cursor_mut();
+
+		while let Some(position) = queue.pop_front() {
+			let Some(level) = light.get(position).map(|light| light.get(channel)) else {
+				continue;
+			};
+
+			for &offset in &offsets {
+				let neighbor = position + offset;
+
+				let Some(block) = blocks.get(neighbor) else {
+					continue;
+				};
+
+				let level = rules.propagate(channel, level, block, offset);
+
+				let Some(light) = light.get_mut(neighbor) else {
+					continue;
+				};
+
+				if level > light.get(channel) {
+					light.set(channel, level);
+
+					queue.push_back(neighbor);
+				}
+			}
+		}
+	}
+	/// Removes the light which may have come from the blocks in `queue`, which had the given levels
+	///
+	/// Returns the blocks from which the darkened area has to be lit again
+	fn decrease<T>(
+		&mut self,
+		world: &Multiform<T, S, W, C, B>,
+		channel: Channel,
+		mut queue: VecDeque<(Point<i32, W>, u8)>,
+	) -> VecDeque<Point<i32, W>>
+	where
+		T: ContiguousMemory,
+		O: Fn(&T::Item) -> u8,
+		E: Fn(&T::Item) -> u8,
+	{
+		let offsets: Vec<_> = math::Neighborhood::VonNeumann(1).offsets().collect();
+
+		let Self { light, rules } = self;
+
+		let mut blocks = world.cursor();
+		let mut above = world.cursor();
+		let mut light = light.cursor_mut();
+
+		let mut relight = VecDeque::new();
+
+		while let Some((position, level)) = queue.pop_front() {
+			for &offset in &offsets {
+				let neighbor = position + offset;
+
+				let Some(block) = blocks.get(neighbor) else {
+					continue;
+				};
+
+				let Some(light) = light.get_mut(neighbor) else {
+					continue;
+				};
+
+				let current = light.get(channel);
+
+				if current == 0 {
+					continue;
+				}
+
+				if current > rules.propagate(channel, level, block, offset) {
+					// lit by something else
+					relight.push_back(neighbor);
+
+					continue;
+				}
+
+				let source = rules.source(&mut above, channel, neighbor, block);
+
+				light.set(channel, source);
+
+				if source > 0 {
+					relight.push_back(neighbor);
+				}
+
+				queue.push_back((neighbor, current));
+			}
+		}
+
+		relight
+	}
+	/// Sets the blocks at `changed` back to their own levels and recomputes the light around them
+	///
+	/// `removed` are blocks which are gone along with their `Chunk`, and the levels they had
+	fn update_many<T>(
+		&mut self,
+		world: &Multiform<T, S, W, C, B>,
+		changed: &[Point<i32, W>],
+		removed: &[(Point<i32, W>, Light)],
+	) where
+		T: ContiguousMemory,
+		O: Fn(&T::Item) -> u8,
+		E: Fn(&T::Item) -> u8,
+	{
+		for channel in [Channel::Block, Channel::Sky] {
+			let mut darkened: VecDeque<_> = removed
+				.iter()
+				.map(|&(position, light)| (position, light.get(channel)))
+				.collect();
+			let mut sources = Vec::new();
+
+			let mut blocks = world.cursor();
+			let mut above = world.cursor();
+			let mut light = self.light.cursor_mut();
+
+			for &position in changed {
+				let Some(block) = blocks.get(position) else {
+					continue;
+				};
+
+				let Some(light) = light.get_mut(position) else {
+					continue;
+				};
+
+				darkened.push_back((position, light.get(channel)));
+				light.set(
+					channel,
+					self.rules.source(&mut above, channel, position, block),
+				);
+				sources.push(position);
+			}
+
+			let mut relight = self.decrease(world, channel, darkened);
+
+			// light flowing in from the neighbors
+			for position in sources {
+				relight.push_back(position);
+				relight.extend(
+					math::Neighborhood::VonNeumann(1)
+						.offsets()
+						.map(|offset| position + offset),
+				);
+			}
+
+			self.increase(world, channel, relight);
+		}
+	}
+	/// Recomputes the light of every `Chunk` of `world` from scratch
+	pub fn relight<T>(&mut self, world: &Multiform<T, S, W, C, B>)
+	where
+		T: ContiguousMemory,
+		O: Fn(&T::Item) -> u8,
+		E: Fn(&T::Item) -> u8,
+	{
+		let shape = self.light.shape;
+
+		self.light = Multiform::new(shape);
+
+		let mut above = world.cursor();
+		let mut blocks = VecDeque::new();
+		let mut sky = VecDeque::new();
+
+		for (chunk, storage) in world.inner.iter() {
+			let mut levels = vec![Light::default(); shape.capacity()];
+
+			for (index, block) in storage.as_slice().iter().enumerate() {
+				let position = shape.chunk_block_to_world(
+					chunk,
+					shape
+						.index_to_position(index)
+						.unwrap_or_else(crate::lazy_unreachable!()),
+				);
+
+				let light = Light {
+					block: self
+						.rules
+						.source(&mut above, Channel::Block, position, block),
+					sky: self.rules.source(&mut above, Channel::Sky, position, block),
+				};
+
+				if light.block > 0 {
+					blocks.push_back(position);
+				}
+
+				if light.sky > 0 {
+					sky.push_back(position);
+				}
+
+				levels[index] = light;
+			}
+
+			self.light.inner.insert(chunk, levels);
+		}
+
+		self.increase(world, Channel::Block, blocks);
+		self.increase(world, Channel::Sky, sky);
+	}
+	/// Updates the light after the block at `position` in `world` was placed, removed or otherwise changed
+	pub fn update<T>(&mut self, world: &Multiform<T, S, W, C, B>, position: Point<i32, W>)
+	where
+		T: ContiguousMemory,
+		O: Fn(&T::Item) -> u8,
+		E: Fn(&T::Item) -> u8,
+	{
+		self.update_many(world, &[position], &[])
+	}
+	/// Updates the light after the `Chunk` at `chunk` was inserted into, removed from or replaced in `world`
+	pub fn update_chunk<T>(&mut self, world: &Multiform<T, S, W, C, B>, chunk: Point<i32, C>)
+	where
+		T: ContiguousMemory,
+		O: Fn(&T::Item) -> u8,
+		E: Fn(&T::Item) -> u8,
+	{
+		let shape = self.light.shape;
+
+		let positions: Vec<_> = shape
+			.bounds()
+			.points()
+			.map(|block| shape.chunk_block_to_world(chunk, block))
+			.collect();
+
+		let removed: Vec<_> = match self.light.inner.remove(chunk) {
+			Some(levels) => positions
+				.iter()
+				.map(|&position| {
+					let index = shape
+						.position_to_index(shape.world_to_block(position))
+						.unwrap_or_else(crate::lazy_unreachable!());
+
+					(position, levels[index])
+				})
+				.collect(),
+			None => Vec::new(),
+		};
+
+		let mut changed = Vec::new();
+
+		if world.inner.get(chunk).is_some() {
+			self.light
+				.inner
+				.insert(chunk, vec![Light::default(); shape.capacity()]);
+
+			changed.extend_from_slice(&positions);
+		}
+
+		// the blocks right below the `Chunk` gain or lose the sky
+		let down = self.rules.offset(-1);
+
+		changed.extend(
+			positions
+				.iter()
+				.map(|&position| position + down)
+				.filter(|&below| shape.world_to_chunk(below) != chunk),
+		);
+
+		self.update_many(world, &changed, &removed);
+	}
+}
+
+const _: () = {
+	use std::fmt::*;
+
+	impl<S, O, E, const W: usize, const C: usize, const B: usize> Debug
+		for LightEngine<S, O, E, W, C, B>
+	where
+		S: Debug,
+	{
+		fn fmt(&self, f: &mut Formatter) -> Result {
+			f.debug_struct("LightEngine")
+				.field("light", &self.light)
+				.field("up", &self.rules.up)
+				.field("max", &self.rules.max)
+				.finish_non_exhaustive()
+		}
+	}
+};
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::Aabb;
+	use crate::world::missing;
+	use crate::world::Uniform;
+	use crate::Chunk;
+
+	const AIR: u8 = 0;
+	const STONE: u8 = 1;
+	const TORCH: u8 = 2;
+	const GLASS: u8 = 3;
+
+	fn opacity(block: &u8) -> u8 {
+		match *block {
+			STONE => 15,
+			GLASS => 2,
+			_ => 0,
+		}
+	}
+
+	fn emission(block: &u8) -> u8 {
+		if *block == TORCH {
+			14
+		} else {
+			0
+		}
+	}
+
+	#[test]
+	fn test_light() {
+		type World = Uniform<Vec<u8>, ct::Uniform<4, 3>, 3>;
+
+		let mut world = World::default();
+
+		let region = Aabb::new(Point::from([0, 0, 0]), Point::from([4, 6, 4]));
+
+		world.fill_region(region, missing::Create, AIR);
+
+		let mut engine = LightEngine::new(Default::default(), 1, opacity, emission);
+
+		engine.relight(&world);
+
+		assert_eq!(engine.get(Point::from([0, 0, 0])).unwrap().sky, 15);
+
+		// a roof over everything
+		world.fill_region(
+			Aabb::new(Point::from([0, 4, 0]), Point::from([4, 5, 4])),
+			missing::Skip,
+			STONE,
+		);
+		*world.get_block_mut(Point::from([0, 2, 0])).unwrap() = TORCH;
+
+		engine.relight(&world);
+
+		assert_eq!(engine.get(Point::from([0, 3, 0])).unwrap().sky, 0);
+		assert_eq!(engine.get(Point::from([0, 5, 0])).unwrap().sky, 15);
+		assert_eq!(engine.get(Point::from([0, 2, 0])).unwrap().block, 14);
+		assert_eq!(engine.get(Point::from([2, 1, 1])).unwrap().block, 10);
+
+		let edits = [
+			([0, 4, 0], AIR),
+			([0, 2, 0], AIR),
+			([1, 1, 1], TORCH),
+			([0, 3, 0], GLASS),
+			([0, 4, 0], STONE),
+			([0, 4, 3], GLASS),
+			([1, 2, 1], STONE),
+			([1, 1, 1], STONE),
+			([3, 4, 1], AIR),
+			([3, 3, 1], TORCH),
+		];
+
+		for (position, block) in edits {
+			let position = Point::from(position);
+
+			*world.get_block_mut(position).unwrap() = block;
+
+			engine.update(&world, position);
+
+			let mut expected = LightEngine::new(Default::default(), 1, opacity, emission);
+
+			expected.relight(&world);
+
+			assert_eq!(
+				engine.light(),
+				expected.light(),
+				"after placing {block} at {position}"
+			);
+		}
+
+		// a hole in the roof lets the sky in
+		assert_eq!(engine.get(Point::from([3, 0, 1])).unwrap().sky, 15);
+		// glass dims the sky light, which then spreads like any other light
+		assert_eq!(engine.get(Point::from([0, 4, 3])).unwrap().sky, 13);
+		assert_eq!(engine.get(Point::from([0, 3, 3])).unwrap().sky, 12);
+	}
+
+	#[test]
+	fn test_update_chunk() {
+		type World = Uniform<Vec<u8>, ct::Uniform<4, 3>, 3>;
+
+		let mut world = World::default();
+
+		world.fill_region(
+			Aabb::new(Point::from([-4, 0, 0]), Point::from([4, 4, 4])),
+			missing::Create,
+			AIR,
+		);
+		*world.get_block_mut(Point::from([1, 1, 1])).unwrap() = TORCH;
+
+		let mut engine = LightEngine::new(Default::default(), 1, opacity, emission);
+
+		engine.relight(&world);
+
+		let mut check = |world: &World, chunk: [i32; 3]| {
+			engine.update_chunk(world, Point::from(chunk));
+
+			let mut expected = LightEngine::new(Default::default(), 1, opacity, emission);
+
+			expected.relight(world);
+
+			assert_eq!(engine.light(), expected.light(), "after changing {chunk:?}");
+		};
+
+		// a stone `Chunk` above shades the one below it
+		world.insert(Point::from([0, 1, 0]), Chunk::from_index(|_| STONE));
+		check(&world, [0, 1, 0]);
+
+		// a `Chunk` with a torch lights its neighbors
+		world.insert(
+			Point::from([-1, 1, 0]),
+			Chunk::from_index(|index| if index == 0 { TORCH } else { AIR }),
+		);
+		check(&world, [-1, 1, 0]);
+
+		world.remove(Point::from([0, 1, 0]));
+		check(&world, [0, 1, 0]);
+
+		world.remove(Point::from([0, 0, 0]));
+		check(&world, [0, 0, 0]);
+	}
+}
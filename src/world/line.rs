@@ -0,0 +1,95 @@
+use super::missing::Missing;
+use super::Multiform;
+use crate::math;
+use crate::math::Point;
+use crate::storage::*;
+use crate::Shape;
+
+/// # Line drawing
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	T::Item: Clone,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Sets every block at a position of `line` to `value`, see [`math::Bresenham`] and [`math::Supercover`]
+	///
+	/// A `Chunk` is only looked up when `line` moves into it. Returns the number of written blocks
+	pub fn draw_line(
+		&mut self,
+		line: impl IntoIterator<Item = Point<i32, W>>,
+		missing: impl Missing<T, S, C, B>,
+		value: T::Item,
+	) -> usize {
+		let shape = self.shape;
+
+		let mut line = line
+			.into_iter()
+			.map(|position| shape.world_to_chunk_block(position))
+			.peekable();
+
+		let mut written = 0;
+
+		while let Some(&(chunk, _)) = line.peek() {
+			let run = std::iter::from_fn(|| {
+				line.next_if(|&(next, _)| next == chunk)
+					.map(|(_, block)| block)
+			});
+
+			let storage = missing.storage(&mut self.inner, shape, chunk);
+
+			let Some(storage) = storage else {
+				run.for_each(drop);
+
+				continue;
+			};
+
+			let slice = storage.as_mut_slice();
+
+			for block in run {
+				let slot = shape
+					.position_to_index(block)
+					.and_then(|index| slice.get_mut(index));
+
+				if let Some(slot) = slot {
+					*slot = value.clone();
+
+					written += 1;
+				}
+			}
+		}
+
+		written
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_draw_line() {
+		let mut world = Uniform::<Vec<u8>, ct::Uniform<4, 2>, 2>::default();
+
+		world.entry(Point::from([0, 0])).or_default();
+
+		let line = || math::Supercover::new(Point::from([-3, 1]), Point::from([6, 2]));
+
+		assert_eq!(world.draw_line(line(), missing::Skip, 1), 6);
+		assert_eq!(world.len(), 1);
+
+		assert_eq!(world.draw_line(line(), missing::Create, 2), 12);
+		assert_eq!(world.len(), 3);
+
+		for position in line() {
+			assert_eq!(world.get_block(position), Some(&2));
+		}
+
+		assert_eq!(world.get_block(Point::from([0, 2])), Some(&0));
+	}
+}
@@ -0,0 +1,358 @@
+use super::Multiform;
+use super::Uniform;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::PositionMap;
+use crate::Shape;
+
+/// Downsampled copies of a [`Uniform`] world, every level has half as many blocks on each axis as the one below
+///
+/// All levels use the `Chunk` shape of the world, a `Chunk` of a level covers `2^D` `Chunk`s of the level below.
+/// Halving every axis of the world needs `Chunk`s along every axis of the world, so worlds whose `Chunk`s are
+/// placed in fewer dimensions or have fewer dimensions than the world, like [`Subform`](super::Subform), are
+/// not supported.
+/// Blocks are `reduce` of the blocks they cover, see [`crate::reduce`]. Blocks covering a missing `Chunk`
+/// are default, `Chunk`s which would only cover missing `Chunk`s are missing
+pub struct Lod<I, S, R, const D: usize> {
+	levels: Vec<Uniform<Vec<I>, S, D>>,
+	shape: S,
+	reduce: R,
+}
+
+impl<I, S, R, const D: usize> Lod<I, S, R, D>
+where
+	S: Shape<D>,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	/// Pyramid of `levels` downsampled levels for worlds with `Chunk`s of `shape`
+	///
+	/// # Panics
+	/// This function panics if any extent of `shape` is odd or if the bounds of `shape` do not start at the origin,
+	/// as the `Chunk`s of a level would not line up with the ones of the level below
+	pub fn new(shape: S, levels: usize, reduce: R) -> Self {
+		assert!(
+			shape.extents().iter().all(|extent| extent % 2 == 0),
+			"`shape` has an odd extent"
+		);
+		assert!(
+			shape.bounds().min == Point::origin(),
+			"`shape` does not start at the origin"
+		);
+
+		Self {
+			levels: (0..levels).map(|_| Multiform::new(shape)).collect(),
+			shape,
+			reduce,
+		}
+	}
+	/// Number of downsampled levels
+	pub fn levels(&self) -> usize {
+		self.levels.len()
+	}
+	/// The world downsampled `level` times, level `0` is the world itself and is not stored
+	pub fn level(&self, level: usize) -> Option<&Uniform<Vec<I>, S, D>> {
+		self.levels.get(level.checked_sub(1)?)
+	}
+	/// Block of `level` at `position`, in the coordinates of that level
+	pub fn get(&self, level: usize, position: Point<i32, D>) -> Option<&I> {
+		self.level(level)?.get_block(position)
+	}
+}
+
+impl<I, S, R, const D: usize> Lod<I, S, R, D>
+where
+	I: Default,
+	S: Shape<D>,
+	R: FnMut(&[&I]) -> I,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	/// Coarse `Chunk` at `chunk` out of the `Chunk`s of `finer` it covers, `None` if all of them are missing
+	fn downsample<T: ContiguousMemory<Item = I>>(
+		shape: S,
+		reduce: &mut R,
+		finer: &Uniform<T, S, D>,
+		chunk: Point<i32, D>,
+	) -> Option<Vec<I>> {
+		let half = shape.extents().map(|extent| (extent / 2) as i32);
+
+		let mut storage = None;
+
+		for corner in Aabb::<i32, D>::from_extents(Vector::repeat(2)).points() {
+			let Some(child) = finer.chunk(Point::from(chunk.coords * 2 + corner.coords)) else {
+				continue;
+			};
+
+			let coarse = child.downsample(&mut *reduce);
+			let offset = corner.coords.component_mul(&half);
+
+			let storage: &mut Vec<I> = storage.get_or_insert_with(|| {
+				std::iter::repeat_with(I::default)
+					.take(shape.capacity())
+					.collect()
+			});
+
+			for (index, block) in coarse.storage.into_iter().enumerate() {
+				let position = coarse
+					.shape
+					.index_to_position(index)
+					.unwrap_or_else(crate::lazy_unreachable!());
+				let index = shape
+					.position_to_index(position + offset)
+					.unwrap_or_else(crate::lazy_unreachable!());
+
+				storage[index] = block;
+			}
+		}
+
+		storage
+	}
+	fn rebuild_level<T: ContiguousMemory<Item = I>>(
+		shape: S,
+		reduce: &mut R,
+		finer: &Uniform<T, S, D>,
+		coarse: &mut Uniform<Vec<I>, S, D>,
+	) {
+		let chunks: PositionMap<(), D> = finer
+			.positions()
+			.map(|chunk| (chunk.map(|c| c.div_euclid(2)), ()))
+			.collect();
+
+		coarse.inner = chunks
+			.iter()
+			.filter_map(|(chunk, _)| Some((chunk, Self::downsample(shape, reduce, finer, chunk)?)))
+			.collect();
+	}
+	/// Recomputes the `Chunk` of `coarse` covering the `Chunk` of `finer` at `chunk`, returns its position
+	fn update_level<T: ContiguousMemory<Item = I>>(
+		shape: S,
+		reduce: &mut R,
+		finer: &Uniform<T, S, D>,
+		coarse: &mut Uniform<Vec<I>, S, D>,
+		chunk: Point<i32, D>,
+	) -> Point<i32, D> {
+		let parent = chunk.map(|c| c.div_euclid(2));
+
+		match Self::downsample(shape, reduce, finer, parent) {
+			Some(storage) => coarse.inner.insert(parent, storage),
+			None => coarse.inner.remove(parent),
+		};
+
+		parent
+	}
+	/// Recomputes every level out of `world` from scratch
+	pub fn rebuild<T: ContiguousMemory<Item = I>>(&mut self, world: &Uniform<T, S, D>) {
+		let Self {
+			levels,
+			shape,
+			reduce,
+		} = self;
+
+		let Some((first, _)) = levels.split_first_mut() else {
+			return;
+		};
+
+		Self::rebuild_level(*shape, reduce, world, first);
+
+		for level in 1..levels.len() {
+			let (finer, coarser) = levels.split_at_mut(level);
+
+			Self::rebuild_level(*shape, reduce, &finer[level - 1], &mut coarser[0]);
+		}
+	}
+	/// Updates the `Chunk`s covering the `Chunk` of `world` at `chunk` after it was changed, inserted or removed
+	pub fn update<T: ContiguousMemory<Item = I>>(
+		&mut self,
+		world: &Uniform<T, S, D>,
+		chunk: Point<i32, D>,
+	) {
+		let Self {
+			levels,
+			shape,
+			reduce,
+		} = self;
+
+		let Some((first, _)) = levels.split_first_mut() else {
+			return;
+		};
+
+		let mut chunk = Self::update_level(*shape, reduce, world, first, chunk);
+
+		for level in 1..levels.len() {
+			let (finer, coarser) = levels.split_at_mut(level);
+
+			chunk = Self::update_level(*shape, reduce, &finer[level - 1], &mut coarser[0], chunk);
+		}
+	}
+}
+
+const _: () = {
+	use std::fmt::*;
+
+	impl<I, S, R, const D: usize> Debug for Lod<I, S, R, D>
+	where
+		I: Debug,
+		S: Debug,
+	{
+		fn fmt(&self, f: &mut Formatter) -> Result {
+			f.debug_struct("Lod")
+				.field("levels", &self.levels)
+				.field("shape", &self.shape)
+				.finish_non_exhaustive()
+		}
+	}
+};
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::reduce;
+	use crate::rt;
+	use crate::world::missing;
+
+	type World = Uniform<Vec<u8>, ct::Uniform<4, 2>, 2>;
+
+	fn world() -> World {
+		let mut world = World::default();
+
+		for chunk in [[0, 0], [1, 0], [-1, 2], [5, -3]] {
+			world.insert(
+				Point::from(chunk),
+				crate::Chunk::from_position(|position: Point<i32, 2>| {
+					(position.x * 7 + position.y * 3 + chunk[0] * 11 + chunk[1] * 5).rem_euclid(10)
+						as u8
+				}),
+			);
+		}
+
+		world
+	}
+
+	/// Compares every level with the maximum of the blocks of `world` it covers
+	fn assert_max<S: Shape<2>>(
+		lod: &Lod<u8, S, impl FnMut(&[&u8]) -> u8, 2>,
+		world: &Uniform<Vec<u8>, S, 2>,
+	) {
+		for level in 1..=lod.levels() {
+			let scale = 1 << level;
+			let coarse = lod.level(level).unwrap();
+
+			for position in Aabb::new(Point::from([-8; 2]), Point::from([8; 2])).points() {
+				let covered = Aabb::new(
+					Point::from(position.coords * scale),
+					Point::from((position.coords + Vector::repeat(1)) * scale),
+				);
+				let blocks: Vec<_> = covered
+					.points()
+					.filter_map(|position| world.get_block(position))
+					.collect();
+
+				// blocks covering missing `Chunk`s are default
+				let chunk = coarse.world_to_chunk(position);
+				let expected = world
+					.positions()
+					.any(|covered| covered.map(|c| c.div_euclid(scale)) == chunk)
+					.then(|| blocks.iter().copied().copied().max().unwrap_or_default());
+
+				assert_eq!(coarse.get_block(position).copied(), expected);
+			}
+		}
+	}
+
+	#[test]
+	fn test_rebuild() {
+		let world = world();
+		let mut lod = Lod::new(ct::Uniform::<4, 2>, 3, reduce::max);
+
+		lod.rebuild(&world);
+
+		assert_eq!(lod.levels(), 3);
+		assert_eq!(lod.level(1).unwrap().len(), 3);
+		assert_eq!(lod.level(3).unwrap().len(), 3);
+		assert!(lod.level(0).is_none());
+		assert!(lod.level(4).is_none());
+		assert_max(&lod, &world);
+	}
+
+	#[test]
+	fn test_update() {
+		let mut world = world();
+		let mut lod = Lod::new(ct::Uniform::<4, 2>, 3, reduce::max);
+
+		lod.rebuild(&world);
+
+		*world.get_block_mut(Point::from([5, 2])).unwrap() = 200;
+		lod.update(&world, Point::from([1, 0]));
+
+		assert_eq!(lod.get(3, Point::from([0, 0])), Some(&200));
+
+		world.remove(Point::from([5, -3]));
+		lod.update(&world, Point::from([5, -3]));
+
+		world
+			.entry(Point::from([-9, 9]))
+			.or_default()
+			.storage
+			.fill(1);
+		lod.update(&world, Point::from([-9, 9]));
+
+		assert_max(&lod, &world);
+
+		let mut rebuilt = Lod::new(ct::Uniform::<4, 2>, 3, reduce::max);
+
+		rebuilt.rebuild(&world);
+
+		for level in 1..=3 {
+			assert_eq!(lod.level(level), rebuilt.level(level));
+		}
+	}
+
+	#[test]
+	fn test_bounded() {
+		let shape = rt::Bounded::new(Point::origin(), Point::from([4, 6]));
+
+		let mut world = Uniform::<Vec<u8>, _, 2>::new(shape);
+
+		world.fill_with(
+			Aabb::new(Point::from([-5, -7]), Point::from([9, 4])),
+			missing::Create,
+			|position| (position.x * 3 + position.y * 5).rem_euclid(23) as u8,
+		);
+
+		let mut lod = Lod::new(shape, 2, reduce::max);
+
+		lod.rebuild(&world);
+
+		assert_max(&lod, &world);
+	}
+
+	#[test]
+	#[should_panic(expected = "does not start at the origin")]
+	fn test_bounded_off_origin() {
+		// the `Chunk`s of level 1 would cover 3 `Chunk`s of the world along each axis
+		Lod::<u8, _, _, 2>::new(
+			rt::Bounded::new(Point::from([-2, -2]), Point::from([2, 2])),
+			1,
+			reduce::max::<u8>,
+		);
+	}
+
+	#[test]
+	fn test_chunk_downsample() {
+		let chunk = crate::Chunk::<Vec<u8>, ct::Uniform<3, 2>, 2>::from_position(|position| {
+			(position.x + 3 * position.y) as u8
+		});
+
+		let coarse = chunk.downsample(reduce::max);
+
+		assert_eq!(coarse.shape.extents(), Vector::from([2, 2]));
+		assert_eq!(coarse.storage, [4, 5, 7, 8]);
+
+		let coarse = chunk.downsample(|blocks| blocks.len());
+
+		assert_eq!(coarse.storage, [4, 2, 2, 1]);
+	}
+}
@@ -0,0 +1,88 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Coordinate;
+use crate::math::Point;
+use crate::mesh;
+use crate::storage::*;
+use crate::Shape;
+
+use num::Float;
+
+/// # Meshing
+impl<T, S, F, const C: usize, const B: usize> Multiform<T, S, 3, C, B>
+where
+	T: ContiguousMemory<Item = F>,
+	S: Shape<B>,
+	F: Coordinate + Float,
+	math::Const<B>: math::DimMax<math::Const<3>, Output = math::Const<3>>,
+	math::Const<C>: math::DimMax<math::Const<3>, Output = math::Const<3>>,
+{
+	/// Extracts the isosurface at `iso` out of the `Chunk` at `chunk` with [`mesh::marching_cubes`]
+	///
+	/// Values beyond the far borders of the `Chunk` are sampled from its neighbors so that the meshes of adjacent `Chunk`s
+	/// line up without seams, cubes reaching into missing `Chunk`s are skipped.
+	/// Vertices are in world coordinates. Returns `None` if the `Chunk` is missing
+	pub fn marching_cubes(&self, chunk: Point<i32, C>, iso: F) -> Option<mesh::Mesh<F>> {
+		self.inner.get(chunk)?;
+
+		let shape = self.shape;
+		let bounds = shape.bounds();
+
+		let region = Aabb::new(
+			shape.chunk_block_to_world(chunk, bounds.min),
+			shape.chunk_block_to_world(chunk, bounds.max),
+		);
+
+		let mut cursor = self.cursor();
+
+		Some(mesh::marching_cubes(region, iso, |position| {
+			cursor.get(position).copied()
+		}))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	use std::collections::HashMap;
+
+	#[test]
+	fn test_marching_cubes() {
+		let mut world = Uniform::<Vec<f32>, ct::Uniform<4, 3>, 3>::default();
+
+		let region = Aabb::new(Point::from([-8; 3]), Point::from([8; 3]));
+
+		world.fill_with(region, missing::Create, |position| {
+			position.map(|c| c as f32 + 0.5).coords.norm() - 4.5
+		});
+
+		// seams would show up as edges used by a single triangle
+		let mut edges = HashMap::new();
+
+		for chunk in world.positions() {
+			let mesh = world.marching_cubes(chunk, 0.0).unwrap();
+
+			for triangle in mesh.triangles() {
+				let key = |i: usize| triangle[i].coords.map(f32::to_bits);
+
+				for i in 0..3 {
+					*edges.entry((key(i), key((i + 1) % 3))).or_insert(0) += 1;
+				}
+			}
+		}
+
+		assert!(!edges.is_empty());
+
+		for (&(a, b), &count) in &edges {
+			assert_eq!(count, 1);
+			assert_eq!(edges.get(&(b, a)), Some(&1));
+		}
+
+		assert!(world.marching_cubes(Point::from([5, 0, 0]), 0.0).is_none());
+	}
+}
@@ -0,0 +1,48 @@
+//! What to do with `Chunk`s which do not exist yet when writing to them
+
+use super::Entry;
+use crate::math::Point;
+use crate::storage::FromFn;
+use crate::PositionMap;
+use crate::Shape;
+
+/// A policy for `Chunk`s which do not exist yet, either [`Skip`] or [`Create`]
+pub trait Missing<T, S, const C: usize, const B: usize>: Copy {
+	/// Storage of the `Chunk` of `inner` at `chunk`, `None` to leave its blocks unwritten
+	fn storage(
+		self,
+		inner: &mut PositionMap<T, C>,
+		shape: S,
+		chunk: Point<i32, C>,
+	) -> Option<&mut T>;
+}
+
+/// Leave the blocks of missing `Chunk`s unwritten
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Skip;
+
+impl<T, S, const C: usize, const B: usize> Missing<T, S, C, B> for Skip {
+	fn storage(self, inner: &mut PositionMap<T, C>, _: S, chunk: Point<i32, C>) -> Option<&mut T> {
+		inner.get_mut(chunk)
+	}
+}
+
+/// Create missing `Chunk`s with [`Entry::or_default`] before writing to them
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Create;
+
+impl<T, S, const C: usize, const B: usize> Missing<T, S, C, B> for Create
+where
+	T: FromFn,
+	T::Item: Default,
+	S: Shape<B>,
+{
+	fn storage(
+		self,
+		inner: &mut PositionMap<T, C>,
+		shape: S,
+		chunk: Point<i32, C>,
+	) -> Option<&mut T> {
+		Some(Entry::from(inner.entry(chunk), shape).or_default().storage)
+	}
+}
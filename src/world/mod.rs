@@ -1,6 +1,47 @@
+//! `W` dimensional worlds made of `Chunk`s
+//!
+//! Methods reading or writing many blocks at once, such as regions, primitives, lines and batches,
+//! resolve each `Chunk` once for all of its blocks instead of once per block
+
 pub mod entry;
+pub mod missing;
+
+mod automaton;
+mod batch;
+mod chunk_neighborhood;
+mod collision;
+mod cursor;
+mod flood;
+mod hierarchical;
+mod light;
+mod line;
+mod lod;
+mod mesh;
+mod neighborhood;
+mod octree;
+mod partition;
+mod path;
+mod primitive;
+mod raycast;
+mod region;
+mod sdf;
 
+pub use automaton::Automaton;
+pub use chunk_neighborhood::ChunkNeighborhood;
+pub use collision::Sweep;
+pub use cursor::Cursor;
+pub use cursor::CursorMut;
 pub use entry::Entry;
+pub use flood::Components;
+pub use hierarchical::Hierarchical;
+pub use hierarchical::HierarchicalEntry;
+pub use light::Light;
+pub use light::LightEngine;
+pub use lod::Lod;
+pub use missing::Missing;
+pub use partition::checkerboard_color;
+pub use path::Path;
+pub use path::PathLimit;
 
 use crate::chunk::Chunk;
 use crate::chunk::ChunkMut;
@@ -44,6 +85,9 @@ where
 	pub fn len(&self) -> usize {
 		self.inner.len()
 	}
+	pub fn is_empty(&self) -> bool {
+		self.inner.is_empty()
+	}
 	pub fn positions(&self) -> impl '_ + Iterator<Item = Point<i32, C>> {
 		self.inner.positions()
 	}
@@ -55,20 +99,20 @@ where
 	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
 	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
 {
-	pub fn iter(&self) -> impl Iterator<Item = (Point<i32, C>, ChunkRef<T, S, B>)> {
+	pub fn iter(&self) -> impl Iterator<Item = (Point<i32, C>, ChunkRef<'_, T, S, B>)> {
 		self.inner
 			.iter()
 			.map(|(p, s)| (p, ChunkRef::new(s, self.shape)))
 	}
-	pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<i32, C>, ChunkMut<T, S, B>)> {
+	pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point<i32, C>, ChunkMut<'_, T, S, B>)> {
 		self.inner
 			.iter_mut()
 			.map(|(p, s)| (p, ChunkMut::new(s, self.shape)))
 	}
-	pub fn chunks(&self) -> impl Iterator<Item = ChunkRef<T, S, B>> {
+	pub fn chunks(&self) -> impl Iterator<Item = ChunkRef<'_, T, S, B>> {
 		self.inner.values().map(|s| ChunkRef::new(s, self.shape))
 	}
-	pub fn chunks_mut(&mut self) -> impl Iterator<Item = ChunkMut<T, S, B>> {
+	pub fn chunks_mut(&mut self) -> impl Iterator<Item = ChunkMut<'_, T, S, B>> {
 		self.inner
 			.values_mut()
 			.map(|s| ChunkMut::new(s, self.shape))
@@ -100,16 +144,29 @@ where
 	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
 	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
 {
-	pub fn chunk(&self, position: Point<i32, C>) -> Option<ChunkRef<T, S, B>> {
+	pub fn chunk(&self, position: Point<i32, C>) -> Option<ChunkRef<'_, T, S, B>> {
 		self.inner
 			.get(position)
 			.map(|storage| ChunkRef::new(storage, self.shape))
 	}
-	pub fn chunk_mut(&mut self, position: Point<i32, C>) -> Option<ChunkMut<T, S, B>> {
+	pub fn chunk_mut(&mut self, position: Point<i32, C>) -> Option<ChunkMut<'_, T, S, B>> {
 		self.inner
 			.get_mut(position)
 			.map(|storage| ChunkMut::new(storage, self.shape))
 	}
+	/// Mutable access to several `Chunk`s at once
+	///
+	/// Returns `None` if any of the `positions` is missing or if they are not distinct
+	pub fn get_many_chunks_mut<const N: usize>(
+		&mut self,
+		positions: [Point<i32, C>; N],
+	) -> Option<[ChunkMut<'_, T, S, B>; N]> {
+		let shape = self.shape;
+
+		self.inner
+			.get_many_mut(positions)
+			.map(|storages| storages.map(|storage| ChunkMut::new(storage, shape)))
+	}
 	pub fn remove(&mut self, position: Point<i32, C>) -> Option<Chunk<T, S, B>> {
 		self.inner
 			.remove(position)
@@ -124,7 +181,7 @@ where
 
 		self.inner.insert(position, storage)
 	}
-	pub fn entry(&mut self, position: Point<i32, C>) -> Entry<T, S, C, B> {
+	pub fn entry(&mut self, position: Point<i32, C>) -> Entry<'_, T, S, C, B> {
 		Entry::from(self.inner.entry(position), self.shape)
 	}
 }
@@ -265,4 +322,31 @@ mod tests {
 			[true]
 		);
 	}
+
+	#[test]
+	fn test_get_many_chunks_mut() {
+		let mut world = Uniform::<[i32; 1], ct::Uniform<1, 2>, 2>::default();
+
+		for x in 0..4 {
+			world.insert(Point::from([x, 0]), Chunk::from_storage([x]));
+		}
+
+		let [a, b, c] = world
+			.get_many_chunks_mut([3, 0, 2].map(|x| Point::from([x, 0])))
+			.unwrap();
+
+		std::mem::swap(a.storage, b.storage);
+		c.storage[0] += 10;
+
+		assert_eq!(world.get_block(Point::from([0, 0])), Some(&3));
+		assert_eq!(world.get_block(Point::from([2, 0])), Some(&12));
+		assert_eq!(world.get_block(Point::from([3, 0])), Some(&0));
+		assert!(world
+			.get_many_chunks_mut([Point::from([1, 0]), Point::from([1, 0])])
+			.is_none());
+		assert!(world
+			.get_many_chunks_mut([Point::from([1, 0]), Point::from([4, 0])])
+			.is_none());
+		assert!(world.get_many_chunks_mut([]).is_some());
+	}
 }
@@ -0,0 +1,83 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Neighborhood;
+use crate::math::Point;
+use crate::storage::*;
+use crate::Shape;
+
+/// # Neighbor access
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Every block in `neighborhood` around `position`, crossing `Chunk` borders
+	///
+	/// Blocks in missing `Chunk`s are `None`
+	pub fn neighborhood(
+		&self,
+		position: Point<i32, W>,
+		neighborhood: Neighborhood,
+	) -> impl Iterator<Item = (Point<i32, W>, Option<&T::Item>)> {
+		let center = self.world_to_chunk(position);
+		let storage = self.inner.get(center);
+
+		neighborhood.offsets().map(move |offset| {
+			let neighbor = position + offset;
+			let (chunk, block) = self.world_to_chunk_block(neighbor);
+
+			let storage = if chunk == center {
+				storage
+			} else {
+				self.inner.get(chunk)
+			};
+
+			let block = storage
+				.and_then(|storage| storage.as_slice().get(self.shape.position_to_index(block)?));
+
+			(neighbor, block)
+		})
+	}
+	/// Every block sharing a face, an edge or a corner with `position`, see [`Self::neighborhood`]
+	pub fn neighbors(
+		&self,
+		position: Point<i32, W>,
+	) -> impl Iterator<Item = (Point<i32, W>, Option<&T::Item>)> {
+		self.neighborhood(position, Neighborhood::Moore(1))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_neighbors() {
+		let mut world = Uniform::<Vec<i32>, ct::Uniform<2, 2>, 2>::default();
+
+		for chunk in [[0, 0], [-1, 0], [-1, -1]] {
+			world
+				.entry(Point::from(chunk))
+				.or_insert_with_key(|chunk| vec![chunk.x * 10 + chunk.y; 4]);
+		}
+
+		let neighbors: Vec<_> = world.neighbors(Point::from([0, 0])).collect();
+
+		assert_eq!(neighbors.len(), 8);
+		assert_eq!(neighbors[0], (Point::from([-1, -1]), Some(&-11)));
+		assert_eq!(neighbors[1], (Point::from([0, -1]), None));
+		assert_eq!(neighbors[3], (Point::from([-1, 0]), Some(&-10)));
+		assert_eq!(neighbors[4], (Point::from([1, 0]), Some(&0)));
+
+		let face: Vec<_> = world
+			.neighborhood(Point::from([-1, 2]), Neighborhood::VonNeumann(1))
+			.map(|(_, block)| block.copied())
+			.collect();
+
+		assert_eq!(face, [Some(-10), None, None, None]);
+	}
+}
@@ -0,0 +1,121 @@
+use super::missing;
+use super::Multiform;
+use super::Uniform;
+use crate::math;
+use crate::math::Aabb;
+use crate::octree;
+use crate::octree::Octree;
+use crate::storage::*;
+use crate::Shape;
+
+/// # Octrees
+impl<T, S, const D: usize> Uniform<T, S, D>
+where
+	S: Shape<D>,
+	math::Const<D>: math::DimMax<math::Const<D>, Output = math::Const<D>>,
+{
+	/// Snapshot of the world as an [`Octree`] over the smallest cube containing every `Chunk`
+	///
+	/// Blocks in missing `Chunk`s are empty, regions without any `Chunk` are never sampled
+	///
+	/// # Panics
+	/// This function panics if the `Chunk`s span more than `2^30` blocks on an axis, see [`Octree::MAX_DEPTH`]
+	pub fn to_octree(&self) -> Octree<T::Item, D>
+	where
+		T: ContiguousMemory,
+		T::Item: Clone + PartialEq,
+	{
+		let shape = self.shape;
+		let bounds = shape.bounds();
+
+		let covered = self
+			.positions()
+			.map(|chunk| {
+				Aabb::new(
+					shape.chunk_block_to_world(chunk, bounds.min),
+					shape.chunk_block_to_world(chunk, bounds.max),
+				)
+			})
+			.reduce(|a, b| a.union(&b))
+			.unwrap_or(Aabb::new(math::Point::origin(), math::Point::origin()));
+
+		let mut cursor = self.cursor();
+
+		Octree::from_fn_uniform(
+			covered.min,
+			octree::depth(covered),
+			|cube| {
+				let chunks = Aabb::new(
+					shape.world_to_chunk(cube.min),
+					shape.world_to_chunk(cube.max - math::Vector::repeat(1))
+						+ math::Vector::repeat(1),
+				);
+
+				self.inner.range(chunks).next().is_none().then_some(None)
+			},
+			|position| cursor.get(position).cloned(),
+		)
+	}
+	/// World with `Chunk`s of `shape` holding the blocks of `octree`
+	///
+	/// `Chunk`s are created where `octree` has blocks which are not empty, their other blocks are default
+	pub fn from_octree(shape: S, octree: &Octree<T::Item, D>) -> Self
+	where
+		T: ContiguousMemoryMut + FromFn,
+		T::Item: Clone + Default,
+	{
+		let mut world = Multiform::new(shape);
+
+		for (aabb, value) in octree.leaves() {
+			world.fill_region(aabb, missing::Create, value.clone());
+		}
+
+		world
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::Point;
+
+	#[test]
+	fn test_round_trip() {
+		let mut world = Uniform::<Vec<u8>, ct::Uniform<4, 3>, 3>::default();
+
+		world.fill_region(
+			Aabb::new(Point::from([-8, -4, -8]), Point::from([8, 0, 8])),
+			missing::Create,
+			1,
+		);
+		world.fill_region(
+			Aabb::new(Point::from([2, 0, 3]), Point::from([5, 3, 4])),
+			missing::Create,
+			2,
+		);
+		world.remove(Point::from([0, -1, 0]));
+		world.insert(Point::from([40, 40, 40]), crate::Chunk::default());
+
+		let octree = world.to_octree();
+
+		assert_eq!(octree.bounds().min, Point::from([-8, -4, -8]));
+		assert_eq!(octree.depth(), 8);
+		assert!(octree.node_count() < 1000);
+
+		let regions = [
+			Aabb::new(Point::from([-10; 3]), Point::from([10; 3])),
+			Aabb::new(Point::from([158; 3]), Point::from([166; 3])),
+		];
+
+		for position in regions.iter().flat_map(|region| region.points()) {
+			assert_eq!(octree.get(position), world.get_block(position));
+		}
+
+		assert_eq!(Uniform::from_octree(ct::Uniform, &octree), world);
+
+		let empty = Uniform::<Vec<u8>, ct::Uniform<4, 3>, 3>::default();
+
+		assert_eq!(empty.to_octree().node_count(), 1);
+	}
+}
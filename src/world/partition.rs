@@ -0,0 +1,145 @@
+use super::ChunkNeighborhood;
+use super::Multiform;
+use crate::chunk::ChunkMut;
+use crate::math;
+use crate::math::Point;
+use crate::PositionMap;
+use crate::Shape;
+
+use rayon::prelude::*;
+
+/// Color of the `Chunk` at `position` in a `2^C` color checkerboard
+///
+/// `Chunk`s of the same color are never adjacent, not even diagonally
+pub fn checkerboard_color<const C: usize>(position: Point<i32, C>) -> usize {
+	position
+		.iter()
+		.enumerate()
+		.fold(0, |acc, (axis, coordinate)| {
+			acc | (coordinate.rem_euclid(2) as usize) << axis
+		})
+}
+
+/// # Partitioning
+impl<T, S: Shape<B>, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Splits the `Chunk`s into `sets` disjoint sets, the `Chunk` at `position` goes into the set `f(position)`
+	///
+	/// # Panics
+	/// This function panics if `f` returns a value greater than or equal to `sets`
+	#[allow(clippy::type_complexity)]
+	pub fn partition_mut(
+		&mut self,
+		sets: usize,
+		mut f: impl FnMut(Point<i32, C>) -> usize,
+	) -> Vec<Vec<(Point<i32, C>, ChunkMut<'_, T, S, B>)>> {
+		let shape = self.shape;
+
+		let mut partition: Vec<Vec<_>> = (0..sets).map(|_| Vec::new()).collect();
+
+		for (position, storage) in self.inner.iter_mut() {
+			partition[f(position)].push((position, ChunkMut::new(storage, shape)));
+		}
+
+		partition
+	}
+	/// Splits the `Chunk`s into `2^C` sets by their [`checkerboard_color`]
+	#[allow(clippy::type_complexity)]
+	pub fn checkerboard_mut(&mut self) -> Vec<Vec<(Point<i32, C>, ChunkMut<'_, T, S, B>)>> {
+		self.partition_mut(1 << C, checkerboard_color)
+	}
+	/// Calls `f` in parallel with the [`ChunkNeighborhood`] of every `Chunk` of each [`checkerboard_color`] in turn
+	///
+	/// `Chunk`s of the same color are never adjacent, so while the `Chunk`s of a color are written their neighbors,
+	/// which all have other colors, are only read. Colors are visited in increasing order, the neighbors of a
+	/// `Chunk` hold the writes of the colors before its own
+	pub fn par_for_each_checkerboard<F>(&mut self, f: F)
+	where
+		T: Send + Sync,
+		S: Send + Sync,
+		F: Fn(ChunkNeighborhood<T, S, C, B>) + Sync,
+	{
+		let shape = self.shape;
+
+		for color in 0..1 << C {
+			let mut chunks = Vec::new();
+			let mut others = PositionMap::new();
+
+			for (position, storage) in self.inner.iter_mut() {
+				if checkerboard_color(position) == color {
+					chunks.push((position, storage));
+				} else {
+					others.insert(position, &*storage);
+				}
+			}
+
+			chunks.into_par_iter().for_each(|(position, storage)| {
+				let center = ChunkMut::new(storage, shape);
+
+				f(ChunkNeighborhood::from_fn(position, center, |neighbor| {
+					others.get(neighbor).copied()
+				}))
+			});
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_checkerboard_color() {
+		for position in math::Aabb::new(Point::from([-3; 3]), Point::from([3; 3])).points() {
+			for offset in math::Neighborhood::Moore(1).offsets() {
+				assert_ne!(
+					checkerboard_color(position),
+					checkerboard_color(position + offset)
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn test_par_for_each_checkerboard() {
+		let mut world = Uniform::<Vec<usize>, ct::Uniform<2, 2>, 2>::default();
+
+		let region = math::Aabb::new(Point::from([-4, -4]), Point::from([4, 4]));
+
+		world.fill_region(region, crate::world::missing::Create, 0);
+
+		assert_eq!(
+			world.checkerboard_mut().iter().map(Vec::len).sum::<usize>(),
+			16
+		);
+
+		// every `Chunk` counts the neighbors which were written before it
+		world.par_for_each_checkerboard(|mut neighborhood| {
+			let written = neighborhood
+				.neighbors()
+				.filter(|(_, chunk)| chunk.storage[0] != 0)
+				.count();
+
+			neighborhood.center_mut().storage.fill(written + 1);
+		});
+
+		for (position, chunk) in world.iter() {
+			let written = math::Neighborhood::Moore(1)
+				.offsets()
+				.map(|offset| position + offset)
+				.filter(|&neighbor| world.chunk(neighbor).is_some())
+				.filter(|&neighbor| checkerboard_color(neighbor) < checkerboard_color(position))
+				.count();
+
+			assert!(chunk.storage.iter().all(|&block| block == written + 1));
+		}
+
+		assert_eq!(world.get_block(Point::from([-4, -4])), Some(&1));
+		assert_eq!(world.get_block(Point::from([-2, -2])), Some(&9));
+	}
+}
@@ -0,0 +1,383 @@
+use super::Multiform;
+use crate::math;
+use crate::math::Neighborhood;
+use crate::math::Point;
+use crate::storage::*;
+use crate::Shape;
+
+use num::Float;
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+
+/// Bound on the work done by a path search
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathLimit<F> {
+	/// Number of blocks which are expanded
+	Nodes(usize),
+	/// Cost of the paths which are explored
+	Distance(F),
+}
+
+/// Path found by [`Multiform::find_path`] or [`Multiform::find_nearest`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path<F, const W: usize> {
+	/// Positions from the start to the goal, both included
+	pub positions: Vec<Point<i32, W>>,
+	/// Sum of the costs of the steps
+	pub cost: F,
+}
+
+/// Entry of the open set, ordered such that the `BinaryHeap` pops the lowest estimate first
+struct Open<F, const W: usize> {
+	estimate: F,
+	distance: F,
+	position: Point<i32, W>,
+}
+
+impl<F: Float, const W: usize> PartialEq for Open<F, W> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<F: Float, const W: usize> Eq for Open<F, W> {}
+
+impl<F: Float, const W: usize> PartialOrd for Open<F, W> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<F: Float, const W: usize> Ord for Open<F, W> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		other
+			.estimate
+			.partial_cmp(&self.estimate)
+			.unwrap_or(Ordering::Equal)
+	}
+}
+
+/// # Pathfinding
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Best first search from `start` until `goal` returns `true`, ordered by the cost so far plus `heuristic`
+	#[allow(clippy::too_many_arguments)]
+	fn search<F: Float>(
+		&self,
+		start: Point<i32, W>,
+		neighborhood: Neighborhood,
+		limit: Option<PathLimit<F>>,
+		mut passable: impl FnMut(&T::Item) -> bool,
+		mut cost: impl FnMut(Point<i32, W>, Point<i32, W>, &T::Item) -> F,
+		mut goal: impl FnMut(Point<i32, W>, &T::Item) -> bool,
+		mut heuristic: impl FnMut(Point<i32, W>) -> F,
+	) -> Option<Path<F, W>> {
+		let offsets: Vec<_> = neighborhood.offsets().collect();
+
+		let mut cursor = self.cursor();
+
+		if !passable(cursor.get(start)?) {
+			return None;
+		}
+
+		let mut distances = HashMap::from([(start, F::zero())]);
+		let mut parents = HashMap::new();
+		let mut open = BinaryHeap::from([Open {
+			estimate: heuristic(start),
+			distance: F::zero(),
+			position: start,
+		}]);
+		let mut expanded = 0;
+
+		while let Some(Open {
+			distance, position, ..
+		}) = open.pop()
+		{
+			// a shorter path to `position` has been expanded already
+			if distances[&position] < distance {
+				continue;
+			}
+
+			let block = cursor
+				.get(position)
+				.unwrap_or_else(crate::lazy_unreachable!());
+
+			if goal(position, block) {
+				let mut positions = vec![position];
+
+				while let Some(&parent) = parents.get(&positions[positions.len() - 1]) {
+					positions.push(parent);
+				}
+
+				positions.reverse();
+
+				return Some(Path {
+					positions,
+					cost: distance,
+				});
+			}
+
+			if limit == Some(PathLimit::Nodes(expanded)) {
+				return None;
+			}
+
+			expanded += 1;
+
+			for &offset in &offsets {
+				let next = position + offset;
+
+				let Some(block) = cursor.get(next) else {
+					continue;
+				};
+
+				if !passable(block) {
+					continue;
+				}
+
+				let distance = distance + cost(position, next, block);
+
+				if matches!(limit, Some(PathLimit::Distance(limit)) if distance > limit) {
+					continue;
+				}
+
+				if distances.get(&next).is_some_and(|&known| known <= distance) {
+					continue;
+				}
+
+				distances.insert(next, distance);
+				parents.insert(next, position);
+				open.push(Open {
+					estimate: distance + heuristic(next),
+					distance,
+					position: next,
+				});
+			}
+		}
+
+		None
+	}
+	/// Cheapest path from `start` to `goal` with A*
+	///
+	/// Only blocks for which `passable` returns `true` are entered, blocks in missing `Chunk`s are impassable.
+	/// `cost(from, to, block)` is the cost of stepping onto `block` at `to`, the path is only guaranteed to be the
+	/// cheapest if it is never less than the euclidean length of the step.
+	/// Returns `None` if `goal` can not be reached within `limit`
+	pub fn find_path<F: Float>(
+		&self,
+		start: Point<i32, W>,
+		goal: Point<i32, W>,
+		neighborhood: Neighborhood,
+		passable: impl FnMut(&T::Item) -> bool,
+		cost: impl FnMut(Point<i32, W>, Point<i32, W>, &T::Item) -> F,
+		limit: Option<PathLimit<F>>,
+	) -> Option<Path<F, W>> {
+		self.search(
+			start,
+			neighborhood,
+			limit,
+			passable,
+			cost,
+			|position, _| position == goal,
+			|position| {
+				let coordinate = |c: i32| F::from(c).unwrap_or_else(crate::lazy_unreachable!());
+
+				// in `F`, the difference and its square may not fit into an `i32`
+				goal.iter()
+					.zip(position.iter())
+					.map(|(&a, &b)| coordinate(a) - coordinate(b))
+					.fold(F::zero(), |length, c| length + c * c)
+					.sqrt()
+			},
+		)
+	}
+	/// Cheapest path from `start` to the closest block for which `goal` returns `true` with Dijkstra's algorithm
+	///
+	/// Like [`Self::find_path`], except that `cost` only has to be non negative
+	pub fn find_nearest<F: Float>(
+		&self,
+		start: Point<i32, W>,
+		goal: impl FnMut(Point<i32, W>, &T::Item) -> bool,
+		neighborhood: Neighborhood,
+		passable: impl FnMut(&T::Item) -> bool,
+		cost: impl FnMut(Point<i32, W>, Point<i32, W>, &T::Item) -> F,
+		limit: Option<PathLimit<F>>,
+	) -> Option<Path<F, W>> {
+		self.search(start, neighborhood, limit, passable, cost, goal, |_| {
+			F::zero()
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::world::Uniform;
+
+	const EMPTY: u8 = 0;
+	const WALL: u8 = 1;
+	const FOOD: u8 = 2;
+
+	/// 12 by 12 blocks in `Chunk`s of 4 by 4 with a wall at `x = 5` which is open at `y = 11`
+	fn world() -> Uniform<Vec<u8>, ct::Uniform<4, 2>, 2> {
+		let mut world = Uniform::default();
+
+		for x in 0..3 {
+			for y in 0..3 {
+				world.entry(Point::from([x, y])).or_default();
+			}
+		}
+
+		for y in 0..11 {
+			*world.get_block_mut(Point::from([5, y])).unwrap() = WALL;
+		}
+
+		world
+	}
+
+	fn length(from: Point<i32, 2>, to: Point<i32, 2>, _: &u8) -> f64 {
+		(to - from).map(f64::from).norm()
+	}
+
+	fn assert_valid(world: &Uniform<Vec<u8>, ct::Uniform<4, 2>, 2>, path: &Path<f64, 2>) {
+		for pair in path.positions.windows(2) {
+			assert!(Neighborhood::Moore(1).contains(pair[1] - pair[0]));
+			assert_ne!(world.get_block(pair[1]), Some(&WALL));
+		}
+
+		let cost: f64 = path
+			.positions
+			.windows(2)
+			.map(|pair| length(pair[0], pair[1], &EMPTY))
+			.sum();
+
+		assert!((cost - path.cost).abs() < 1e-9);
+	}
+
+	#[test]
+	fn test_find_path() {
+		let world = world();
+		let (start, goal) = (Point::from([1, 1]), Point::from([9, 1]));
+		let passable = |&block: &u8| block != WALL;
+
+		let path = world
+			.find_path(
+				start,
+				goal,
+				Neighborhood::VonNeumann(1),
+				passable,
+				length,
+				None,
+			)
+			.unwrap();
+
+		// around the wall, through several `Chunk`s
+		assert_eq!(path.cost, 28.0);
+		assert_eq!(path.positions.len(), 29);
+		assert_eq!(path.positions[0], start);
+		assert_eq!(path.positions[28], goal);
+		assert_valid(&world, &path);
+
+		let diagonal = world
+			.find_path(start, goal, Neighborhood::Moore(1), passable, length, None)
+			.unwrap();
+
+		assert!(diagonal.cost < path.cost);
+		assert_valid(&world, &diagonal);
+
+		let limit = |limit| {
+			world.find_path(
+				start,
+				goal,
+				Neighborhood::VonNeumann(1),
+				passable,
+				length,
+				Some(limit),
+			)
+		};
+
+		assert!(limit(PathLimit::Nodes(20)).is_none());
+		assert!(limit(PathLimit::Distance(27.0)).is_none());
+		assert_eq!(limit(PathLimit::Distance(28.0)), Some(path));
+
+		// missing `Chunk`s are impassable
+		let outside = Point::from([20, 1]);
+
+		assert!(world
+			.find_path(
+				start,
+				outside,
+				Neighborhood::Moore(1),
+				passable,
+				length,
+				None
+			)
+			.is_none());
+	}
+
+	#[test]
+	fn test_find_path_far_away() {
+		let world = world();
+		let passable = |&block: &u8| block != WALL;
+
+		// the squared distance to these goals overflows an `i32`
+		for goal in [[100_000, 1], [i32::MAX, i32::MIN]] {
+			assert!(world
+				.find_path(
+					Point::from([1, 1]),
+					Point::from(goal),
+					Neighborhood::Moore(1),
+					passable,
+					length,
+					None
+				)
+				.is_none());
+		}
+	}
+
+	#[test]
+	fn test_find_nearest() {
+		let mut world = world();
+
+		*world.get_block_mut(Point::from([9, 1])).unwrap() = FOOD;
+		*world.get_block_mut(Point::from([2, 6])).unwrap() = FOOD;
+
+		let food = |_, &block: &u8| block == FOOD;
+		let passable = |&block: &u8| block != WALL;
+
+		let path = world
+			.find_nearest(
+				Point::from([1, 1]),
+				food,
+				Neighborhood::VonNeumann(1),
+				passable,
+				length,
+				None,
+			)
+			.unwrap();
+
+		assert_eq!(path.positions.last(), Some(&Point::from([2, 6])));
+		assert_eq!(path.cost, 6.0);
+
+		// the other food is on this side of the wall
+		let path = world
+			.find_nearest(
+				Point::from([8, 1]),
+				food,
+				Neighborhood::VonNeumann(1),
+				passable,
+				length,
+				None,
+			)
+			.unwrap();
+
+		assert_eq!(path.positions, [[8, 1], [9, 1]].map(Point::from));
+	}
+}
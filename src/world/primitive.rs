@@ -0,0 +1,110 @@
+use super::missing::Missing;
+use super::Multiform;
+use crate::math;
+use crate::math::Point;
+use crate::math::Primitive;
+use crate::storage::*;
+use crate::Shape;
+
+/// # Rasterization
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Sets every block inside of `primitive` to `f(world_position)`
+	///
+	/// Missing `Chunk`s are only created if at least one of their blocks is inside of `primitive`
+	pub fn rasterize(
+		&mut self,
+		primitive: impl Primitive<W>,
+		missing: impl Missing<T, S, C, B>,
+		mut f: impl FnMut(Point<i32, W>) -> T::Item,
+	) {
+		let shape = self.shape;
+
+		for (chunk, part) in self.region_chunks(primitive.bounds()) {
+			let inside = |&block: &Point<i32, B>| {
+				primitive.contains(shape.chunk_block_to_world(chunk, block))
+			};
+
+			if self.inner.get(chunk).is_none() && !part.points().any(|block| inside(&block)) {
+				continue;
+			}
+
+			let Some(storage) = missing.storage(&mut self.inner, shape, chunk) else {
+				continue;
+			};
+
+			let slice = storage.as_mut_slice();
+
+			for block in part.points().filter(inside) {
+				let index = shape
+					.position_to_index(block)
+					.unwrap_or_else(crate::lazy_unreachable!());
+
+				slice[index] = f(shape.chunk_block_to_world(chunk, block));
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::primitive::Ball;
+	use crate::math::primitive::Cylinder;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_rasterize() {
+		let mut world = Uniform::<Vec<u8>, ct::Uniform<4, 3>, 3>::default();
+
+		let ball = Ball::new(Point::from([1.0, 2.5, -0.5]), 3.5);
+
+		world.rasterize(ball, missing::Create, |_| 1);
+
+		for position in ball.bounds().expand(math::Vector::repeat(1)).points() {
+			let expected = if ball.contains(position) { 1 } else { 0 };
+
+			assert_eq!(world.get_block(position).copied().unwrap_or(0), expected);
+		}
+
+		world.rasterize(&ball, missing::Skip, |position| position.x as u8);
+
+		assert_eq!(world.get_block(Point::from([3, 2, 0])), Some(&3));
+	}
+
+	#[test]
+	fn test_rasterize_creates_touched_chunks() {
+		let mut world = Uniform::<Vec<u8>, ct::Uniform<4, 2>, 2>::default();
+
+		// a thin diagonal cylinder whose bounds cover `5 * 5` `Chunk`s
+		let cylinder = Cylinder::new(Point::from([0.0, 0.0]), Point::from([19.0, 19.0]), 0.5);
+
+		world.rasterize(cylinder, missing::Create, |_| 1);
+
+		let touched: Vec<_> = world
+			.region_chunks(cylinder.bounds())
+			.filter(|&(chunk, part)| {
+				part.points()
+					.any(|block| cylinder.contains(world.chunk_block_to_world(chunk, block)))
+			})
+			.map(|(chunk, _)| chunk)
+			.collect();
+
+		assert!(touched.len() < 5 * 5);
+		assert_eq!(world.positions().collect::<Vec<_>>(), touched);
+		assert_eq!(
+			world
+				.region_blocks(cylinder.bounds())
+				.filter(|&(_, &block)| block == 1)
+				.count(),
+			cylinder.positions().count()
+		);
+	}
+}
@@ -0,0 +1,91 @@
+use super::Multiform;
+use crate::math;
+use crate::math::raycast::Cell;
+use crate::math::raycast::Traversal;
+use crate::math::Coordinate;
+use crate::math::Point;
+use crate::math::Vector;
+use crate::storage::*;
+use crate::Shape;
+
+use num::Float;
+
+/// # Raycasting
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// The first block along the ray from `origin` towards `direction` for which `predicate` returns `true`
+	///
+	/// Blocks are visited in the order of [`Traversal`], blocks in missing `Chunk`s are skipped.
+	/// `Chunk`s are only looked up when the ray crosses into them
+	pub fn raycast<F: Coordinate + Float>(
+		&self,
+		origin: Point<F, W>,
+		direction: Vector<F, W>,
+		max_distance: F,
+		mut predicate: impl FnMut(Point<i32, W>, &T::Item) -> bool,
+	) -> Option<(Cell<F, W>, &T::Item)> {
+		let mut cursor = self.cursor();
+
+		Traversal::new(origin, direction, max_distance).find_map(|cell| {
+			let block = cursor.get(cell.position)?;
+
+			predicate(cell.position, block).then_some((cell, block))
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::Aabb;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_raycast() {
+		let mut world = Uniform::<Vec<bool>, ct::Uniform<4, 3>, 3>::default();
+
+		let floor = Aabb::new(Point::from([-8, -1, -8]), Point::from([8, 0, 8]));
+
+		world.fill_region(floor, missing::Create, true);
+		world.remove(Point::from([0, -1, 0]));
+
+		let (cell, _) = world
+			.raycast(
+				Point::from([-2.5, 3.5, 0.5]),
+				Vector::from([0.0, -1.0, 0.0]),
+				10.0,
+				|_, &solid| solid,
+			)
+			.unwrap();
+
+		assert_eq!(cell.position, Point::from([-3, -1, 0]));
+		assert_eq!(cell.normal, Vector::from([0, 1, 0]));
+		assert_eq!(cell.distance, 3.5);
+
+		// straight through the removed `Chunk`
+		assert!(world
+			.raycast(
+				Point::from([1.5, 3.5, 1.5]),
+				Vector::from([0.0, -1.0, 0.0]),
+				10.0,
+				|_, &solid| solid,
+			)
+			.is_none());
+
+		assert!(world
+			.raycast(
+				Point::from([-2.5, 3.5, 0.5]),
+				Vector::from([0.0, -1.0, 0.0]),
+				3.0,
+				|_, &solid| solid,
+			)
+			.is_none());
+	}
+}
@@ -0,0 +1,337 @@
+use super::missing::Missing;
+use super::Multiform;
+use crate::math;
+use crate::math::Aabb;
+use crate::math::Point;
+use crate::storage::*;
+use crate::Shape;
+
+/// # Region manipulation
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Splits `region` into the parts covered by each `Chunk` it intersects
+	///
+	/// Yields the position of every such `Chunk`, whether it exists or not, along with the part of `region` inside of it in `Chunk` local coordinates
+	pub fn region_chunks(
+		&self,
+		region: Aabb<i32, W>,
+	) -> impl Iterator<Item = (Point<i32, C>, Aabb<i32, B>)> {
+		let shape = self.shape;
+		let bounds = shape.bounds();
+
+		let chunks = if region.is_empty() {
+			Aabb::new(Point::origin(), Point::origin())
+		} else {
+			Aabb::new(
+				shape.world_to_chunk(region.min),
+				shape.world_to_chunk(region.max.map(|max| max - 1)) + math::Vector::repeat(1),
+			)
+		};
+
+		chunks.points().filter_map(move |chunk| {
+			let covered = Aabb::new(
+				shape.chunk_block_to_world(chunk, bounds.min),
+				shape.chunk_block_to_world(chunk, bounds.max),
+			);
+
+			let part = region.intersection(&covered)?;
+
+			Some((
+				chunk,
+				Aabb::new(
+					shape.world_to_block(part.min),
+					shape.world_to_block(part.max.map(|max| max - 1)) + math::Vector::repeat(1),
+				),
+			))
+		})
+	}
+}
+
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Every block in `region` which is in an existing `Chunk`
+	pub fn region_blocks(
+		&self,
+		region: Aabb<i32, W>,
+	) -> impl Iterator<Item = (Point<i32, W>, &T::Item)> {
+		let shape = self.shape;
+
+		self.region_chunks(region)
+			.filter_map(|(chunk, part)| Some((chunk, part, self.inner.get(chunk)?.as_slice())))
+			.flat_map(move |(chunk, part, slice)| {
+				part.points().map(move |block| {
+					let index = shape
+						.position_to_index(block)
+						.unwrap_or_else(crate::lazy_unreachable!());
+
+					(shape.chunk_block_to_world(chunk, block), &slice[index])
+				})
+			})
+	}
+}
+
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Calls `f` with every block in `region` which is in an existing `Chunk`
+	pub fn for_each_region_block_mut(
+		&mut self,
+		region: Aabb<i32, W>,
+		mut f: impl FnMut(Point<i32, W>, &mut T::Item),
+	) {
+		let shape = self.shape;
+
+		for (chunk, part) in self.region_chunks(region) {
+			let Some(storage) = self.inner.get_mut(chunk) else {
+				continue;
+			};
+
+			let slice = storage.as_mut_slice();
+
+			for block in part.points() {
+				let index = shape
+					.position_to_index(block)
+					.unwrap_or_else(crate::lazy_unreachable!());
+
+				f(shape.chunk_block_to_world(chunk, block), &mut slice[index]);
+			}
+		}
+	}
+}
+
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemoryMut,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	pub(super) fn for_each_region_part_mut(
+		&mut self,
+		region: Aabb<i32, W>,
+		missing: impl Missing<T, S, C, B>,
+		mut f: impl FnMut(Point<i32, C>, Aabb<i32, B>, &mut [T::Item]),
+	) {
+		for (chunk, part) in self.region_chunks(region) {
+			let storage = missing.storage(&mut self.inner, self.shape, chunk);
+
+			if let Some(storage) = storage {
+				f(chunk, part, storage.as_mut_slice())
+			}
+		}
+	}
+	/// Sets every block in `region` to `f(world_position)`
+	pub fn fill_with(
+		&mut self,
+		region: Aabb<i32, W>,
+		missing: impl Missing<T, S, C, B>,
+		mut f: impl FnMut(Point<i32, W>) -> T::Item,
+	) {
+		let shape = self.shape;
+
+		self.for_each_region_part_mut(region, missing, |chunk, part, slice| {
+			for block in part.points() {
+				let index = shape
+					.position_to_index(block)
+					.unwrap_or_else(crate::lazy_unreachable!());
+
+				slice[index] = f(shape.chunk_block_to_world(chunk, block));
+			}
+		})
+	}
+	/// Sets every block in `region` to `value`
+	pub fn fill_region(
+		&mut self,
+		region: Aabb<i32, W>,
+		missing: impl Missing<T, S, C, B>,
+		value: T::Item,
+	) where
+		T::Item: Clone,
+	{
+		self.fill_with(region, missing, |_| value.clone())
+	}
+	/// Sets every block in `region` for which `predicate` returns `true` to `value`
+	///
+	/// Returns the number of replaced blocks
+	pub fn replace_in_region(
+		&mut self,
+		region: Aabb<i32, W>,
+		missing: impl Missing<T, S, C, B>,
+		mut predicate: impl FnMut(&T::Item) -> bool,
+		value: T::Item,
+	) -> usize
+	where
+		T::Item: Clone,
+	{
+		let shape = self.shape;
+
+		let mut replaced = 0;
+
+		self.for_each_region_part_mut(region, missing, |_, part, slice| {
+			for block in part.points() {
+				let index = shape
+					.position_to_index(block)
+					.unwrap_or_else(crate::lazy_unreachable!());
+
+				if predicate(&slice[index]) {
+					slice[index] = value.clone();
+
+					replaced += 1;
+				}
+			}
+		});
+
+		replaced
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::rt;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	fn region(min: [i32; 2], max: [i32; 2]) -> Aabb<i32, 2> {
+		Aabb::new(Point::from(min), Point::from(max))
+	}
+
+	#[test]
+	fn test_region_chunks() {
+		let world = Uniform::<Vec<u8>, ct::Uniform<4, 2>, 2>::default();
+
+		let region = region([-3, -1], [5, 6]);
+
+		let parts: Vec<_> = world.region_chunks(region).collect();
+
+		assert_eq!(parts.len(), 3 * 3);
+		assert_eq!(
+			parts.iter().map(|(_, part)| part.volume()).sum::<i32>(),
+			region.volume()
+		);
+		assert!(parts.contains(&(
+			Point::from([-1, -1]),
+			Aabb::new(Point::from([1, 3]), Point::from([4, 4]))
+		)));
+
+		let world = &world;
+
+		let mut covered: Vec<_> = parts
+			.iter()
+			.flat_map(|&(chunk, part)| {
+				part.points()
+					.map(move |block| world.chunk_block_to_world(chunk, block))
+			})
+			.collect();
+
+		covered.sort_by_key(|point| (point.x, point.y));
+		covered.dedup();
+
+		assert_eq!(covered.len(), region.points().len());
+		assert!(covered.iter().all(|&point| region.contains(point)));
+	}
+
+	#[test]
+	fn test_region_chunks_bounded() {
+		let world = Uniform::<Vec<u8>, rt::Bounded<2>, 2>::new(rt::Bounded::centered(
+			math::Vector::from([1, 1]),
+		));
+
+		let parts: Vec<_> = world.region_chunks(region([-1, -1], [3, 2])).collect();
+
+		assert_eq!(
+			parts,
+			[
+				(Point::from([0, 0]), region([-1, -1], [2, 2])),
+				(Point::from([1, 0]), region([-1, -1], [0, 2])),
+			]
+		);
+	}
+
+	#[test]
+	fn test_region_blocks() {
+		let mut world = Uniform::<Vec<i32>, ct::Uniform<2, 2>, 2>::default();
+
+		world.entry(Point::from([0, 0])).or_default();
+		world.entry(Point::from([-1, 0])).or_default();
+
+		let region = region([-1, 0], [2, 3]);
+
+		world.for_each_region_block_mut(region, |position, block| *block = position.x);
+
+		let blocks: Vec<_> = world.region_blocks(region).collect();
+
+		assert_eq!(blocks.len(), 6);
+		assert!(blocks
+			.iter()
+			.all(|&(position, &block)| position.x == block && position.y < 2));
+		assert_eq!(world.get_block(Point::from([-2, 0])), Some(&0));
+	}
+
+	#[test]
+	fn test_fill_region() {
+		let mut world = Uniform::<Vec<i32>, ct::Uniform<4, 3>, 3>::default();
+
+		let region = Aabb::new(Point::from([-2, 0, 0]), Point::from([4, 1, 1]));
+
+		world.fill_region(region, missing::Skip, 1);
+
+		assert!(world.is_empty());
+
+		world.fill_region(region, missing::Create, 1);
+
+		assert_eq!(world.len(), 2);
+		assert_eq!(world.region_blocks(region).count(), 6);
+		assert!(world.region_blocks(region).all(|(_, &block)| block == 1));
+		assert_eq!(world.get_block(Point::from([-3, 0, 0])), Some(&0));
+		assert_eq!(world.get_block(Point::from([0, 1, 0])), Some(&0));
+
+		world.fill_with(region, missing::Skip, |position| position.x);
+
+		assert_eq!(world.get_block(Point::from([3, 0, 0])), Some(&3));
+
+		let replaced = world.replace_in_region(
+			region.expand(math::Vector::repeat(1)),
+			missing::Skip,
+			|&block| block <= 0,
+			7,
+		);
+
+		// `7 * 2 * 2` existing blocks of which `x = 1, 2, 3` on the first row are positive
+		assert_eq!(replaced, 7 * 2 * 2 - 3);
+		assert_eq!(world.get_block(Point::from([-2, 0, 0])), Some(&7));
+		assert_eq!(world.get_block(Point::from([1, 0, 0])), Some(&1));
+	}
+
+	#[test]
+	fn test_fill_region_skip() {
+		// blocks without a default value can only be written to existing `Chunk`s
+		#[derive(Debug, Clone, PartialEq)]
+		struct Block(i32);
+
+		let mut world = Uniform::<Vec<Block>, ct::Uniform<2, 2>, 2>::default();
+
+		world.inner.insert(Point::from([0, 0]), vec![Block(0); 4]);
+		world.fill_region(region([-2, 0], [2, 1]), missing::Skip, Block(1));
+
+		assert_eq!(world.len(), 1);
+		assert_eq!(
+			world.inner.get(Point::from([0, 0])),
+			Some(&vec![Block(1), Block(1), Block(0), Block(0)])
+		);
+	}
+}
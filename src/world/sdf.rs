@@ -0,0 +1,100 @@
+use super::Multiform;
+use crate::chunk::Chunk;
+use crate::math;
+use crate::math::Aabb;
+use crate::rt;
+use crate::sdf;
+use crate::storage::*;
+use crate::Shape;
+
+use num::Float;
+
+/// # Signed distance fields
+impl<T, S, const W: usize, const C: usize, const B: usize> Multiform<T, S, W, C, B>
+where
+	T: ContiguousMemory,
+	S: Shape<B>,
+	math::Const<B>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+	math::Const<C>: math::DimMax<math::Const<W>, Output = math::Const<W>>,
+{
+	/// Signed euclidean distance field over `region` of the blocks for which `inside` returns `true`, see [`crate::sdf`]
+	///
+	/// Blocks in missing `Chunk`s are outside, blocks outside of `region` are not taken into account
+	pub fn signed_distance<F: Float>(
+		&self,
+		region: Aabb<i32, W>,
+		mut inside: impl FnMut(&T::Item) -> bool,
+	) -> Chunk<Vec<F>, rt::Bounded<W>, W> {
+		let shape = rt::Bounded::from(region);
+
+		let mut cursor = self.cursor();
+
+		let mask: Vec<_> = Aabb::from(shape)
+			.points()
+			.map(|position| cursor.get(position).is_some_and(&mut inside))
+			.collect();
+
+		Chunk::new(sdf::signed_distance(shape.extents(), &mask), shape)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ct;
+	use crate::math::Point;
+	use crate::world::missing;
+	use crate::world::Uniform;
+
+	#[test]
+	fn test_signed_distance() {
+		let mut world = Uniform::<Vec<bool>, ct::Uniform<4, 3>, 3>::default();
+
+		// a floor below `y = 0` with a pillar on it, across several `Chunk`s
+		world.fill_region(
+			Aabb::new(Point::from([-6, -4, -6]), Point::from([6, 0, 6])),
+			missing::Create,
+			true,
+		);
+		world.fill_region(
+			Aabb::new(Point::from([1, 0, 1]), Point::from([3, 5, 3])),
+			missing::Create,
+			true,
+		);
+
+		let region = Aabb::new(Point::from([-6, -4, -6]), Point::from([6, 8, 6]));
+		let sdf = world.signed_distance::<f32>(region, |&solid| solid);
+
+		assert_eq!(sdf.block(Point::from([-5, 0, -5])), Some(&0.5));
+		assert_eq!(sdf.block(Point::from([-5, 3, -5])), Some(&3.5));
+		assert_eq!(sdf.block(Point::from([-5, -3, -5])), Some(&-2.5));
+		assert_eq!(sdf.block(Point::from([3, 4, 2])), Some(&0.5));
+		assert_eq!(sdf.block(Point::from([2, 6, 2])), Some(&1.5));
+		assert_eq!(
+			sdf.block(Point::from([2, 5, 3])),
+			Some(&(2f32.sqrt() - 0.5))
+		);
+
+		// blocks sharing a face with solid blocks: the floor surface, the pillar sides and its top
+		let surface = sdf.iter().filter(|&&distance| distance == 0.5).count();
+
+		assert_eq!(surface, 12 * 12 - 2 * 2 + 4 * 2 * 4 + 2 * 2);
+
+		// carving a smooth hole into the floor
+		let mut terrain = sdf.clone();
+		let hole: Chunk<Vec<f32>, _, 3> = Chunk::from_shape_position(*sdf.shape(), |position| {
+			(position - Point::from([-3, -1, -3]))
+				.map(|c| c as f32)
+				.norm() - 2.0
+		});
+
+		terrain.combine(&hole, |&a, &b| crate::sdf::smooth_subtraction(a, b, 1.0));
+
+		assert!(terrain.block(Point::from([-3, -1, -3])).unwrap() > &0.0);
+		assert_eq!(
+			terrain.block(Point::from([5, -3, 5])),
+			sdf.block(Point::from([5, -3, 5]))
+		);
+		assert!(terrain.iter().zip(sdf.iter()).all(|(a, b)| a >= b));
+	}
+}